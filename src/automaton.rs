@@ -0,0 +1,84 @@
+use crate::{GenericTrie, TrieNode};
+
+/* A deterministic automaton over chars that a Trie can be intersected with.
+ * Regex DFAs, Levenshtein automata or custom filters can be plugged into search() by implementing it. */
+pub trait Automaton {
+    type State;
+
+    /* The state before any char has been consumed. */
+    fn start(&self) -> Self::State;
+
+    /* The state reached from 'state' by consuming ch. */
+    fn accept(&self, state: &Self::State, ch: char) -> Self::State;
+
+    /* Whether or not the chars consumed to reach 'state' form a match. */
+    fn is_match(&self, state: &Self::State) -> bool;
+
+    /* Whether or not a match can still be reached from 'state'. Subtrees are skipped as soon as this
+     * returns false, so it should be as precise as cheaply possible. */
+    fn can_match(&self, state: &Self::State) -> bool;
+}
+
+impl<A: Automaton + ?Sized> Automaton for &A {
+    type State = A::State;
+
+    fn start(&self) -> Self::State {
+        (**self).start()
+    }
+
+    fn accept(&self, state: &Self::State, ch: char) -> Self::State {
+        (**self).accept(state, ch)
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        (**self).is_match(state)
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        (**self).can_match(state)
+    }
+}
+
+impl<V> GenericTrie<char, V> {
+    /* Returns every key accepted by the automaton, paired with its value, in lexicographic order.
+     * The Trie and the automaton are walked together, so subtrees in which the automaton can no
+     * longer match are never visited. */
+    pub fn search<A: Automaton>(&self, automaton: A) -> Vec<(Vec<char>, &V)> {
+        let mut matches = vec![];
+
+        let start = automaton.start();
+        if automaton.can_match(&start) {
+            Self::walk_automaton(&automaton, &start, &mut vec![], &self.root, &mut matches);
+        }
+
+        matches
+    }
+
+    /* Recursively walks all the child nodes of 'node' whose chars keep the automaton alive,
+     * while feeding the keys it matches and their values into matches. */
+    fn walk_automaton<'a, A: Automaton>(
+        automaton: &A,
+        state: &A::State,
+        tmp_key: &mut Vec<char>,
+        node: &'a TrieNode<char, V>,
+        matches: &mut Vec<(Vec<char>, &'a V)>,
+    ) {
+        for (ch, next_node) in node.map.iter() {
+            let next_state = automaton.accept(state, *ch);
+            if !automaton.can_match(&next_state) {
+                continue;
+            }
+
+            tmp_key.push(*ch);
+            if let Some(value) = &next_node.value {
+                if automaton.is_match(&next_state) {
+                    matches.push((tmp_key.clone(), value));
+                }
+            }
+            if !next_node.map.is_empty() {
+                Self::walk_automaton(automaton, &next_state, tmp_key, next_node, matches);
+            }
+            tmp_key.pop();
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::{cmp::Ordering, error::Error, fmt, io};
+
+use crate::{iter::BytesKeys, Trie, TrieNode};
+
+/* Layout of the binary format (all integers little-endian):
+ *
+ * header:  magic "TRIE" | version: u16 | reserved: u16 | node count: u32 | key count: u32 | checksum: u32
+ * nodes:   node count records of label: u32 | first child: u32 | child count << 1 | terminal: u32
+ *
+ * Nodes are stored in breadth-first order with the root first, so the children of every node occupy
+ * a contiguous range of records, sorted by label. The checksum is the 32-bit FNV-1a hash of the node records. */
+const MAGIC: &[u8; 4] = b"TRIE";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 20;
+const RECORD_LEN: usize = 12;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FormatError {
+    /* The data ends before the header or the node records do. */
+    Truncated,
+    BadMagic,
+    UnsupportedVersion(u16),
+    ChecksumMismatch,
+    /* The checksum is valid, but a record points outside of the data or holds an invalid label. */
+    Malformed,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::Truncated => write!(f, "trie data is truncated"),
+            FormatError::BadMagic => write!(f, "data is not a serialized trie"),
+            FormatError::UnsupportedVersion(v) => write!(f, "unsupported trie format version {v}"),
+            FormatError::ChecksumMismatch => write!(f, "trie checksum mismatch"),
+            FormatError::Malformed => write!(f, "trie data is malformed"),
+        }
+    }
+}
+
+impl Error for FormatError {}
+
+/* A single node of a flattened Trie. Children of a node are found at
+ * first_child..first_child + child_count in breadth-first order. */
+pub(crate) struct FlatNode {
+    pub(crate) label: char,
+    pub(crate) first_child: u32,
+    pub(crate) child_count: u32,
+    pub(crate) terminal: bool,
+}
+
+/* Numbers the nodes below 'root' in breadth-first order. */
+pub(crate) fn flatten<V>(root: &TrieNode<char, V>) -> Vec<FlatNode> {
+    let mut queue = vec![('\0', root)];
+    let mut flat_nodes = Vec::new();
+
+    let mut i = 0;
+    while let Some(&(label, node)) = queue.get(i) {
+        flat_nodes.push(FlatNode {
+            label,
+            first_child: queue.len() as u32,
+            child_count: node.map.len() as u32,
+            terminal: node.value.is_some(),
+        });
+        queue.extend(node.map.iter().map(|(ch, next_node)| (*ch, next_node)));
+        i += 1;
+    }
+
+    flat_nodes
+}
+
+/* 32-bit FNV-1a. */
+fn checksum(data: &[u8]) -> u32 {
+    data.iter().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x01000193)
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+impl Trie {
+    /* Serializes the Trie into the binary format, which can be loaded again with TrieBytes::new(). */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        // Writing into a Vec cannot fail.
+        self.write_bytes(&mut bytes).unwrap();
+        bytes
+    }
+
+    /* Like to_bytes(), but writes the binary format to 'writer'. */
+    pub fn write_bytes<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        let flat_nodes = flatten(&self.map.trie.root);
+
+        let mut records = Vec::with_capacity(flat_nodes.len() * RECORD_LEN);
+        for node in &flat_nodes {
+            records.extend_from_slice(&u32::from(node.label).to_le_bytes());
+            records.extend_from_slice(&node.first_child.to_le_bytes());
+            records.extend_from_slice(
+                &(node.child_count << 1 | u32::from(node.terminal)).to_le_bytes(),
+            );
+        }
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&0u16.to_le_bytes())?;
+        writer.write_all(&(flat_nodes.len() as u32).to_le_bytes())?;
+        writer.write_all(&(self.size() as u32).to_le_bytes())?;
+        writer.write_all(&checksum(&records).to_le_bytes())?;
+        writer.write_all(&records)
+    }
+}
+
+/* A read-only Trie operating directly on data in the binary format, e.g. a memory-mapped file.
+ * Loading only validates the data; nodes are never decoded into a separate structure. */
+#[derive(Debug, Clone, Copy)]
+pub struct TrieBytes<'a> {
+    records: &'a [u8],
+    size: usize,
+}
+
+impl<'a> TrieBytes<'a> {
+    /* Checks the header, the checksum and the bounds of all node records. */
+    pub fn new(data: &'a [u8]) -> Result<Self, FormatError> {
+        if data.len() < HEADER_LEN {
+            return Err(FormatError::Truncated);
+        }
+        if &data[..4] != MAGIC {
+            return Err(FormatError::BadMagic);
+        }
+        let version = u16::from_le_bytes([data[4], data[5]]);
+        if version != VERSION {
+            return Err(FormatError::UnsupportedVersion(version));
+        }
+
+        let node_count = read_u32(data, 8) as usize;
+        let size = read_u32(data, 12) as usize;
+        let records = data[HEADER_LEN..]
+            .get(..node_count * RECORD_LEN)
+            .ok_or(FormatError::Truncated)?;
+        if checksum(records) != read_u32(data, 16) {
+            return Err(FormatError::ChecksumMismatch);
+        }
+
+        if node_count == 0 {
+            return Err(FormatError::Malformed);
+        }
+
+        /* Children must come after their parent, which rules out cycles that would make iteration endless. */
+        let trie_bytes = Self { records, size };
+        for i in 0..node_count as u32 {
+            let (first_child, child_count, _) = trie_bytes.node(i);
+            if char::from_u32(trie_bytes.label(i)).is_none()
+                || first_child as usize + child_count as usize > node_count
+                || (child_count > 0 && first_child <= i)
+            {
+                return Err(FormatError::Malformed);
+            }
+        }
+
+        Ok(trie_bytes)
+    }
+
+    fn label(&self, i: u32) -> u32 {
+        read_u32(self.records, i as usize * RECORD_LEN)
+    }
+
+    /* Returns the first child, the number of children and whether or not node i ends a string. */
+    pub(crate) fn node(&self, i: u32) -> (u32, u32, bool) {
+        let offset = i as usize * RECORD_LEN;
+        let meta = read_u32(self.records, offset + 8);
+        (read_u32(self.records, offset + 4), meta >> 1, meta & 1 == 1)
+    }
+
+    /* Returns the char leading into node i. */
+    pub(crate) fn char(&self, i: u32) -> char {
+        char::from_u32(self.label(i)).unwrap()
+    }
+
+    /* Returns the index of the child of node i labeled ch, by binary search over its sorted children. */
+    fn child(&self, i: u32, ch: char) -> Option<u32> {
+        let (first_child, child_count, _) = self.node(i);
+        let (mut lo, mut hi) = (first_child, first_child + child_count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.label(mid).cmp(&u32::from(ch)) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+
+    /* Returns the index of the node pointed to by the last character of s. */
+    fn find_node(&self, s: &str) -> Option<u32> {
+        let mut node = 0;
+        for ch in s.chars() {
+            node = self.child(node, ch)?;
+        }
+        Some(node)
+    }
+
+    /* Returns the number of strings in the Trie. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /* Whether or not s is present in the Trie. */
+    pub fn contains(&self, s: &str) -> bool {
+        self.find_node(s).is_some_and(|node| self.node(node).2)
+    }
+
+    /* Whether or not at least one string with a prefix s is present in the Trie. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.find_node(s).is_some()
+    }
+
+    /* Returns an iterator lazily yielding all strings present in the Trie, in lexicographic order. */
+    pub fn iter(&self) -> BytesKeys<'a> {
+        BytesKeys::new(*self, String::new(), Some(0))
+    }
+
+    /* Like iter(). However, only strings that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> BytesKeys<'a> {
+        BytesKeys::new(*self, s.into(), self.find_node(s))
+    }
+
+    /* Builds and returns a vector holding all strings that share a common prefix s, in lexicographic order. */
+    pub fn as_vec_pref(&self, s: &str) -> Vec<String> {
+        if s.is_empty() {
+            return vec![];
+        }
+        self.iter_prefix(s).collect()
+    }
+}
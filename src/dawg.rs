@@ -0,0 +1,205 @@
+use std::{collections::HashMap, mem};
+
+use crate::iter::DawgKeys;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct DawgNode {
+    /* Outgoing edges sorted by label. */
+    pub(crate) edges: Vec<(char, u32)>,
+    pub(crate) terminal: bool,
+}
+
+impl DawgNode {
+    fn new() -> Self {
+        Self {
+            edges: vec![],
+            terminal: false,
+        }
+    }
+}
+
+/* Builds a minimal Dawg from strings inserted in lexicographic order, using the incremental algorithm
+ * by Daciuk et al.: once a string diverges from its predecessor, the nodes only reachable through the
+ * predecessor's suffix can never change again and are merged with equivalent nodes seen before. */
+#[derive(Debug)]
+pub struct DawgBuilder {
+    nodes: Vec<DawgNode>,
+    /* Maps every minimized node to its index, so that equivalent nodes can be found. */
+    register: HashMap<DawgNode, u32>,
+    /* The path (parent, label, child) of the last string that has not been minimized yet. */
+    unchecked: Vec<(u32, char, u32)>,
+    previous: String,
+    size: usize,
+}
+
+impl DawgBuilder {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![DawgNode::new()],
+            register: HashMap::new(),
+            unchecked: vec![],
+            previous: String::new(),
+            size: 0,
+        }
+    }
+
+    /* Adds s to the Dawg being built. Strings must be inserted in lexicographic order.
+     * Returns true only if s has been added, i.e. it is not empty and greater than the previous string. */
+    pub fn insert(&mut self, s: &str) -> bool {
+        if s.is_empty() || (self.size > 0 && s <= self.previous.as_str()) {
+            return false;
+        }
+
+        let common = s
+            .chars()
+            .zip(self.previous.chars())
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.minimize(common);
+
+        let mut node = self.unchecked.last().map_or(0, |&(_, _, child)| child);
+        for ch in s.chars().skip(common) {
+            let child = self.nodes.len() as u32;
+            self.nodes.push(DawgNode::new());
+            self.nodes[node as usize].edges.push((ch, child));
+            self.unchecked.push((node, ch, child));
+            node = child;
+        }
+        self.nodes[node as usize].terminal = true;
+
+        self.previous.clear();
+        self.previous.push_str(s);
+        self.size += 1;
+        true
+    }
+
+    /* Replaces the unchecked nodes deeper than down_to with equivalent registered nodes,
+     * or registers them if there are none. */
+    fn minimize(&mut self, down_to: usize) {
+        for &(parent, ch, child) in self.unchecked[down_to..].iter().rev() {
+            match self.register.get(&self.nodes[child as usize]) {
+                Some(&existing) => {
+                    // Edges are added in order, so the edge to 'child' is the last one of 'parent'.
+                    let edge = self.nodes[parent as usize].edges.last_mut().unwrap();
+                    debug_assert_eq!(edge.0, ch);
+                    edge.1 = existing;
+                }
+                None => {
+                    self.register
+                        .insert(self.nodes[child as usize].clone(), child);
+                }
+            }
+        }
+        self.unchecked.truncate(down_to);
+    }
+
+    /* Minimizes the remaining nodes and returns the finished Dawg.
+     * Nodes that have been replaced by equivalent ones are dropped. */
+    pub fn finish(mut self) -> Dawg {
+        self.minimize(0);
+
+        /* Renumber the nodes reachable from the root, which keeps the root at index 0. */
+        let mut new_index = vec![u32::MAX; self.nodes.len()];
+        let mut order = vec![0];
+        new_index[0] = 0;
+        let mut i = 0;
+        while let Some(&node) = order.get(i) {
+            for &(_, child) in &self.nodes[node as usize].edges {
+                if new_index[child as usize] == u32::MAX {
+                    new_index[child as usize] = order.len() as u32;
+                    order.push(child);
+                }
+            }
+            i += 1;
+        }
+
+        let nodes = order
+            .into_iter()
+            .map(|node| {
+                let mut node = mem::replace(&mut self.nodes[node as usize], DawgNode::new());
+                for edge in &mut node.edges {
+                    edge.1 = new_index[edge.1 as usize];
+                }
+                node
+            })
+            .collect();
+
+        Dawg {
+            nodes,
+            size: self.size,
+        }
+    }
+}
+
+/* A directed acyclic word graph: a Trie in which equivalent subtrees (e.g. shared suffixes) are stored
+ * only once. It is immutable and built with a DawgBuilder or from any of the sources a Trie is built from. */
+#[derive(Debug, Clone)]
+pub struct Dawg {
+    nodes: Vec<DawgNode>,
+    size: usize,
+}
+
+impl Dawg {
+    pub(crate) fn node(&self, i: u32) -> &DawgNode {
+        &self.nodes[i as usize]
+    }
+
+    /* Returns the index of the node pointed to by the last character of s. */
+    fn find_node(&self, s: &str) -> Option<u32> {
+        let mut node = 0;
+        for ch in s.chars() {
+            let edges = &self.nodes[node as usize].edges;
+            let i = edges.binary_search_by_key(&ch, |&(label, _)| label).ok()?;
+            node = edges[i].1;
+        }
+        Some(node)
+    }
+
+    /* Returns the number of strings in the Dawg. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /* Returns the number of nodes the Dawg consists of after minimization. */
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /* Whether or not s is present in the Dawg. */
+    pub fn contains(&self, s: &str) -> bool {
+        self.find_node(s)
+            .is_some_and(|node| self.nodes[node as usize].terminal)
+    }
+
+    /* Whether or not at least one string with a prefix s is present in the Dawg. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.find_node(s).is_some()
+    }
+
+    /* Returns an iterator lazily yielding all strings present in the Dawg, in lexicographic order. */
+    pub fn iter(&self) -> DawgKeys<'_> {
+        DawgKeys::new(self, String::new(), Some(0))
+    }
+
+    /* Like iter(). However, only strings that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> DawgKeys<'_> {
+        DawgKeys::new(self, s.into(), self.find_node(s))
+    }
+
+    /* Builds and returns a vector holding all strings present in the Dawg, in lexicographic order. */
+    pub fn as_vec(&self) -> Vec<String> {
+        self.iter().collect()
+    }
+
+    /* Like as_vec(). However, the returned vector only holds strings that share a common prefix s. */
+    pub fn as_vec_pref(&self, s: &str) -> Vec<String> {
+        if s.is_empty() {
+            return vec![];
+        }
+        self.iter_prefix(s).collect()
+    }
+}
@@ -0,0 +1,165 @@
+use std::mem;
+
+use crate::{GenericTrie, TrieMap, TrieNode};
+
+/* A view into a single key of a Trie, which is either present (Occupied) or not (Vacant).
+ * Obtained from entry(), it allows inserting or updating a value with a single walk along the key. */
+#[derive(Debug)]
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/* An entry whose key is present in the Trie. */
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, K, V> {
+    key: Vec<K>,
+    value: &'a mut V,
+}
+
+/* An entry whose key is not present in the Trie. It holds on to the part of the path that
+ * already exists, so that inserting only has to add the missing nodes. */
+#[derive(Debug)]
+pub struct VacantEntry<'a, K, V> {
+    key: Vec<K>,
+    /* The subtree counts of the nodes above 'node', which all grow by one on insertion. */
+    counts: Vec<&'a mut usize>,
+    /* The deepest existing node along key, pointed to by key[..depth]. */
+    node: &'a mut TrieNode<K, V>,
+    depth: usize,
+}
+
+impl<K, V> GenericTrie<K, V>
+where
+    K: Ord + Clone,
+{
+    /* Returns the entry for key, for in-place manipulation of its value.
+     * Returns None if key is empty, since empty keys are not stored. */
+    pub fn entry<I>(&mut self, key: I) -> Option<Entry<'_, K, V>>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let key: Vec<K> = key.into_iter().collect();
+        if key.is_empty() {
+            return None;
+        }
+
+        let mut counts = vec![];
+        let mut node = &mut self.root;
+        let mut depth = 0;
+        while depth < key.len() && node.map.contains_key(&key[depth]) {
+            // The count and the children are borrowed separately, so the counts can be kept while descending.
+            let TrieNode { map, count, .. } = node;
+            counts.push(count);
+            node = map.get_mut(&key[depth]).unwrap();
+            depth += 1;
+        }
+
+        /* Matching on node.value directly would keep node borrowed in the vacant case as well. */
+        let occupied = depth == key.len() && node.value.is_some();
+        if occupied {
+            let value = node.value.as_mut().unwrap();
+            return Some(Entry::Occupied(OccupiedEntry { key, value }));
+        }
+        Some(Entry::Vacant(VacantEntry {
+            key,
+            counts,
+            node,
+            depth,
+        }))
+    }
+}
+
+impl<V> TrieMap<V> {
+    /* Returns the entry for the key s, e.g. *map.entry(word)?.or_insert(0) += 1 to count words.
+     * Returns None if s is empty, since empty keys are not stored. */
+    pub fn entry(&mut self, s: &str) -> Option<Entry<'_, char, V>> {
+        self.trie.entry(s.chars())
+    }
+}
+
+impl<'a, K: Ord + Clone, V> Entry<'a, K, V> {
+    pub fn key(&self) -> &[K] {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /* Inserts default if the key is vacant. Returns a mutable reference to the value in the entry. */
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /* Like or_insert(), but the value is only computed if the key is vacant. */
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /* Like or_insert(), but inserts the default value of V. */
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /* Calls f on the value if the key is occupied. Returns the entry for further chaining. */
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &[K] {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        self.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    /* Like get_mut(), but the reference lives as long as the borrow of the Trie. */
+    pub fn into_mut(self) -> &'a mut V {
+        self.value
+    }
+
+    /* Replaces the value in the entry. Returns the old value. */
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.value, value)
+    }
+}
+
+impl<'a, K: Ord + Clone, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &[K] {
+        &self.key
+    }
+
+    /* Stores value under the key of the entry. Returns a mutable reference to it. */
+    pub fn insert(self, value: V) -> &'a mut V {
+        for count in self.counts {
+            *count += 1;
+        }
+
+        let mut node = self.node;
+        node.count += 1;
+        for elem in &self.key[self.depth..] {
+            node = node.map.entry(elem.clone()).or_insert_with(TrieNode::new);
+            node.count += 1;
+        }
+        node.value.insert(value)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{binary::flatten, iter::FrozenKeys, Trie};
+
+/* An immutable Trie whose nodes are packed into flat arrays instead of one map per node.
+ * Nodes are numbered in breadth-first order with the root at 0, so the children of node i are exactly
+ * the nodes child_starts[i]..child_starts[i + 1], sorted by label. */
+#[derive(Debug, Clone)]
+pub struct FrozenTrie {
+    child_starts: Vec<u32>,
+    /* The char leading into each node. The root's label is unused. */
+    labels: Vec<char>,
+    /* Bit i is set if node i ends a string. */
+    terminal: Vec<u64>,
+    size: usize,
+}
+
+impl Trie {
+    /* Packs the Trie into a FrozenTrie, which only supports lookups but uses far less memory. */
+    pub fn freeze(self) -> FrozenTrie {
+        let flat_nodes = flatten(&self.map.trie.root);
+
+        let mut child_starts = Vec::with_capacity(flat_nodes.len() + 1);
+        let mut labels = Vec::with_capacity(flat_nodes.len());
+        let mut terminal = vec![0; flat_nodes.len().div_ceil(64)];
+        for (i, node) in flat_nodes.iter().enumerate() {
+            child_starts.push(node.first_child);
+            labels.push(node.label);
+            if node.terminal {
+                terminal[i / 64] |= 1 << (i % 64);
+            }
+        }
+        child_starts.push(flat_nodes.len() as u32);
+
+        FrozenTrie {
+            child_starts,
+            labels,
+            terminal,
+            size: self.size(),
+        }
+    }
+}
+
+impl FrozenTrie {
+    /* Returns the range of node indices holding the children of node i. */
+    pub(crate) fn children(&self, i: u32) -> (u32, u32) {
+        (
+            self.child_starts[i as usize],
+            self.child_starts[i as usize + 1],
+        )
+    }
+
+    pub(crate) fn label(&self, i: u32) -> char {
+        self.labels[i as usize]
+    }
+
+    pub(crate) fn is_terminal(&self, i: u32) -> bool {
+        self.terminal[i as usize / 64] & (1 << (i % 64)) != 0
+    }
+
+    /* Returns the index of the node pointed to by the last character of s. */
+    fn find_node(&self, s: &str) -> Option<u32> {
+        let mut node = 0;
+        for ch in s.chars() {
+            let (start, end) = self.children(node);
+            let labels = &self.labels[start as usize..end as usize];
+            node = start + labels.binary_search(&ch).ok()? as u32;
+        }
+        Some(node)
+    }
+
+    /* Returns the number of strings in the FrozenTrie. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /* Whether or not s is present in the FrozenTrie. */
+    pub fn contains(&self, s: &str) -> bool {
+        self.find_node(s).is_some_and(|node| self.is_terminal(node))
+    }
+
+    /* Whether or not at least one string with a prefix s is present in the FrozenTrie. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.find_node(s).is_some()
+    }
+
+    /* Returns an iterator lazily yielding all strings present in the FrozenTrie, in lexicographic order. */
+    pub fn iter(&self) -> FrozenKeys<'_> {
+        FrozenKeys::new(self, String::new(), Some(0))
+    }
+
+    /* Like iter(). However, only strings that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> FrozenKeys<'_> {
+        FrozenKeys::new(self, s.into(), self.find_node(s))
+    }
+
+    /* Builds and returns a vector holding all strings present in the FrozenTrie, in lexicographic order. */
+    pub fn as_vec(&self) -> Vec<String> {
+        self.iter().collect()
+    }
+
+    /* Like as_vec(). However, the returned vector only holds strings that share a common prefix s. */
+    pub fn as_vec_pref(&self, s: &str) -> Vec<String> {
+        if s.is_empty() {
+            return vec![];
+        }
+        self.iter_prefix(s).collect()
+    }
+}
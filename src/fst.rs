@@ -0,0 +1,248 @@
+use std::{
+    collections::HashMap,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::iter::FstStream;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FstEdge {
+    pub(crate) label: char,
+    pub(crate) output: u64,
+    pub(crate) target: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FstNode {
+    /* Outgoing edges sorted by label. */
+    pub(crate) edges: Vec<FstEdge>,
+    pub(crate) is_final: bool,
+    /* Added to the output of a key ending in this node. */
+    pub(crate) final_output: u64,
+}
+
+impl FstNode {
+    fn new() -> Self {
+        Self {
+            edges: vec![],
+            is_final: false,
+            final_output: 0,
+        }
+    }
+}
+
+/* Builds an Fst from (key, value) pairs inserted in lexicographic order of their keys.
+ * Works like the DawgBuilder, but additionally moves the values onto the edges: every edge carries the part
+ * of the values that all keys passing through it have in common, so that equivalent suffixes can be shared. */
+#[derive(Debug)]
+pub struct FstBuilder {
+    nodes: Vec<FstNode>,
+    register: HashMap<FstNode, u32>,
+    /* The nodes along the last key that have not been minimized yet, starting at the root.
+     * The last edge of each of them leads to the next one; its target is set once that one is minimized. */
+    unfinished: Vec<FstNode>,
+    previous: Vec<char>,
+    size: usize,
+}
+
+impl FstBuilder {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            register: HashMap::new(),
+            unfinished: vec![FstNode::new()],
+            previous: vec![],
+            size: 0,
+        }
+    }
+
+    /* Adds key with its value to the Fst being built. Keys must be inserted in lexicographic order.
+     * Returns true only if key has been added, i.e. it is not empty and greater than the previous key. */
+    pub fn insert(&mut self, key: &str, value: u64) -> bool {
+        let key: Vec<char> = key.chars().collect();
+        if key.is_empty() || (self.size > 0 && key <= self.previous) {
+            return false;
+        }
+
+        let common = key
+            .iter()
+            .zip(&self.previous)
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.minimize(common);
+
+        /* Along the shared prefix, every edge keeps only what it has in common with the new value.
+         * The rest is pushed down to the outputs of the next node. */
+        let mut value = value;
+        for i in 0..common {
+            let edge = self.unfinished[i].edges.last_mut().unwrap();
+            let shared = edge.output.min(value);
+            let rest = edge.output - shared;
+            edge.output = shared;
+            value -= shared;
+
+            if rest > 0 {
+                let next_node = &mut self.unfinished[i + 1];
+                for edge in &mut next_node.edges {
+                    edge.output += rest;
+                }
+                if next_node.is_final {
+                    next_node.final_output += rest;
+                }
+            }
+        }
+
+        for (i, ch) in key[common..].iter().enumerate() {
+            self.unfinished.last_mut().unwrap().edges.push(FstEdge {
+                label: *ch,
+                output: if i == 0 { value } else { 0 },
+                target: u32::MAX,
+            });
+            self.unfinished.push(FstNode::new());
+        }
+        self.unfinished.last_mut().unwrap().is_final = true;
+
+        self.previous = key;
+        self.size += 1;
+        true
+    }
+
+    /* Replaces the unfinished nodes deeper than down_to with equivalent registered nodes,
+     * or registers them if there are none. */
+    fn minimize(&mut self, down_to: usize) {
+        while self.unfinished.len() > down_to + 1 {
+            let node = self.unfinished.pop().unwrap();
+            let target = self.register(node);
+            self.unfinished
+                .last_mut()
+                .unwrap()
+                .edges
+                .last_mut()
+                .unwrap()
+                .target = target;
+        }
+    }
+
+    fn register(&mut self, node: FstNode) -> u32 {
+        if let Some(&index) = self.register.get(&node) {
+            return index;
+        }
+        let index = self.nodes.len() as u32;
+        self.nodes.push(node.clone());
+        self.register.insert(node, index);
+        index
+    }
+
+    /* Minimizes the remaining nodes and returns the finished Fst. */
+    pub fn finish(mut self) -> Fst {
+        self.minimize(0);
+        let root = self.unfinished.pop().unwrap();
+        let root_index = self.nodes.len() as u32;
+        self.nodes.push(root);
+
+        Fst {
+            nodes: self.nodes,
+            root: root_index,
+            size: self.size,
+        }
+    }
+}
+
+/* A finite-state transducer: an immutable, minimal map from strings to u64 values sharing both
+ * prefixes and suffixes of its keys. It is built with an FstBuilder or from a TrieMap<u64>. */
+#[derive(Debug, Clone)]
+pub struct Fst {
+    nodes: Vec<FstNode>,
+    root: u32,
+    size: usize,
+}
+
+impl Fst {
+    pub(crate) fn node(&self, i: u32) -> &FstNode {
+        &self.nodes[i as usize]
+    }
+
+    /* Returns the index of the node pointed to by the last character of s,
+     * together with the sum of the outputs along the way. */
+    fn find_node(&self, s: &str) -> Option<(u32, u64)> {
+        let mut node = self.root;
+        let mut output = 0;
+        for ch in s.chars() {
+            let edges = &self.nodes[node as usize].edges;
+            let i = edges.binary_search_by_key(&ch, |edge| edge.label).ok()?;
+            output += edges[i].output;
+            node = edges[i].target;
+        }
+        Some((node, output))
+    }
+
+    /* Returns the number of keys in the Fst. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /* Returns the number of nodes the Fst consists of after minimization. */
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /* Returns the value associated with key. */
+    pub fn get(&self, key: &str) -> Option<u64> {
+        let (node, output) = self.find_node(key)?;
+        let node = &self.nodes[node as usize];
+        node.is_final.then_some(output + node.final_output)
+    }
+
+    /* Whether or not key is present in the Fst. */
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /* Whether or not at least one key with a prefix s is present in the Fst. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.find_node(s).is_some()
+    }
+
+    /* Returns a stream lazily yielding all keys in lexicographic order, paired with their values. */
+    pub fn iter(&self) -> FstStream<'_> {
+        FstStream::new(
+            self,
+            vec![],
+            Some((self.root, 0)),
+            Bound::Unbounded,
+            Bound::Unbounded,
+        )
+    }
+
+    /* Like iter(). However, only keys that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> FstStream<'_> {
+        FstStream::new(
+            self,
+            s.chars().collect(),
+            self.find_node(s),
+            Bound::Unbounded,
+            Bound::Unbounded,
+        )
+    }
+
+    /* Like iter(). However, only keys within the given range are yielded, e.g. fst.range("mar".."mat").
+     * Subtrees entirely below the start of the range are skipped, and the stream ends at its end. */
+    pub fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> FstStream<'_> {
+        let to_chars = |bound: Bound<&&str>| match bound {
+            Bound::Included(s) => Bound::Included(s.chars().collect()),
+            Bound::Excluded(s) => Bound::Excluded(s.chars().collect()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        FstStream::new(
+            self,
+            vec![],
+            Some((self.root, 0)),
+            to_chars(range.start_bound()),
+            to_chars(range.end_bound()),
+        )
+    }
+}
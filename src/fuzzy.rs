@@ -0,0 +1,97 @@
+use crate::{GenericTrie, TrieNode};
+
+/* State shared across the recursive walk of fuzzy_search(). */
+struct FuzzySearch<'q, 'a, K, V> {
+    query: &'q [K],
+    max_distance: usize,
+    transpositions: bool,
+    key: Vec<K>,
+    matches: Vec<(Vec<K>, &'a V, usize)>,
+}
+
+impl<K: Ord + Clone, V> GenericTrie<K, V> {
+    /* Returns every key within Levenshtein distance max_distance of query, paired with its value
+     * and its distance, in lexicographic order.
+     * One row of the edit distance matrix is computed per node, and branches whose row
+     * already exceeds max_distance are skipped entirely. */
+    pub fn fuzzy_search<I>(&self, query: I, max_distance: usize) -> Vec<(Vec<K>, &V, usize)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.fuzzy_search_impl(query, max_distance, false)
+    }
+
+    /* Like fuzzy_search(), but swapping two adjacent elements counts as a single edit
+     * (optimal string alignment distance). */
+    pub fn fuzzy_search_with_transpositions<I>(
+        &self,
+        query: I,
+        max_distance: usize,
+    ) -> Vec<(Vec<K>, &V, usize)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.fuzzy_search_impl(query, max_distance, true)
+    }
+
+    fn fuzzy_search_impl<I>(
+        &self,
+        query: I,
+        max_distance: usize,
+        transpositions: bool,
+    ) -> Vec<(Vec<K>, &V, usize)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let query: Vec<K> = query.into_iter().collect();
+        let mut search = FuzzySearch {
+            query: &query,
+            max_distance,
+            transpositions,
+            key: vec![],
+            matches: vec![],
+        };
+
+        let first_row: Vec<usize> = (0..=query.len()).collect();
+        search.walk(&self.root, &[], &first_row);
+
+        search.matches
+    }
+}
+
+impl<'a, K: Ord + Clone, V> FuzzySearch<'_, 'a, K, V> {
+    /* Computes the row of every child of 'node' from the rows of 'node' (row) and its parent (prev_row),
+     * recording matches and descending into children that can still lead to one. */
+    fn walk(&mut self, node: &'a TrieNode<K, V>, prev_row: &[usize], row: &[usize]) {
+        for (elem, next_node) in node.map.iter() {
+            let mut next_row = Vec::with_capacity(row.len());
+            next_row.push(row[0] + 1);
+            for j in 1..row.len() {
+                let cost = usize::from(self.query[j - 1] != *elem);
+                let mut distance = (row[j] + 1).min(next_row[j - 1] + 1).min(row[j - 1] + cost);
+
+                if self.transpositions
+                    && j > 1
+                    && !prev_row.is_empty()
+                    && self.query[j - 2] == *elem
+                    && self.key.last() == Some(&self.query[j - 1])
+                {
+                    distance = distance.min(prev_row[j - 2] + 1);
+                }
+                next_row.push(distance);
+            }
+
+            self.key.push(elem.clone());
+            let distance = next_row[next_row.len() - 1];
+            if let Some(value) = &next_node.value {
+                if distance <= self.max_distance {
+                    self.matches.push((self.key.clone(), value, distance));
+                }
+            }
+            if next_row.iter().min().unwrap() <= &self.max_distance {
+                self.walk(next_node, row, &next_row);
+            }
+            self.key.pop();
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use std::{collections::BTreeMap, mem, ops::RangeBounds};
+
+use crate::iter::{Iter, Prefixes, Range};
+
+/* Nodes never outlive the last key below them, so two Tries holding the same keys (and values)
+ * always consist of the same nodes, and comparing them structurally compares their keys. */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrieNode<K, V> {
+    pub(crate) map: BTreeMap<K, TrieNode<K, V>>,
+    pub(crate) value: Option<V>,
+    /* The number of keys stored in the subtree rooted at this node, including the node itself. */
+    pub(crate) count: usize,
+}
+
+impl<K, V> TrieNode<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+            value: None,
+            count: 0,
+        }
+    }
+}
+
+/* A Trie over keys made up of arbitrary elements K (chars, bytes, token IDs, path segments...),
+ * associating a value with every stored key. */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GenericTrie<K, V> {
+    pub(crate) root: TrieNode<K, V>,
+}
+
+impl<K, V> GenericTrie<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            root: TrieNode::new(),
+        }
+    }
+
+    /* Returns the number of keys in the Trie. */
+    pub fn size(&self) -> usize {
+        self.root.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.count == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = TrieNode::new();
+    }
+
+    /* Associates value with key. Empty keys are not stored.
+     * Returns the value previously associated with key, if any. */
+    pub fn insert<I>(&mut self, key: I, value: V) -> Option<V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let key: Vec<K> = key.into_iter().collect();
+        if key.is_empty() {
+            return None;
+        }
+
+        // The subtree counts along the path only change if key is new.
+        if let Some(old_value) = self.get_mut(key.iter().cloned()) {
+            return Some(mem::replace(old_value, value));
+        }
+
+        let mut node = &mut self.root;
+        node.count += 1;
+        for elem in key {
+            node = node.map.entry(elem).or_insert_with(TrieNode::new);
+            node.count += 1;
+        }
+        node.value = Some(value);
+        None
+    }
+
+    /* Returns the node pointed to by the last element of key, if the path exists. */
+    fn find_node<I>(&self, key: I) -> Option<&TrieNode<K, V>>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut node = &self.root;
+        for elem in key {
+            node = node.map.get(&elem)?;
+        }
+        Some(node)
+    }
+
+    /* Returns a reference to the value associated with key. */
+    pub fn get<I>(&self, key: I) -> Option<&V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.find_node(key)?.value.as_ref()
+    }
+
+    /* Returns a mutable reference to the value associated with key. */
+    pub fn get_mut<I>(&mut self, key: I) -> Option<&mut V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut node = &mut self.root;
+        for elem in key {
+            node = node.map.get_mut(&elem)?;
+        }
+        node.value.as_mut()
+    }
+
+    /* Removes key from the Trie.
+     * Returns the value associated with key up until removal. */
+    pub fn remove<I>(&mut self, key: I) -> Option<V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let key: Vec<K> = key.into_iter().collect();
+        if key.is_empty() || !self.contains_key(key.iter().cloned()) {
+            return None;
+        }
+
+        let mut node = &mut self.root;
+        node.count -= 1;
+        for (i, elem) in key.iter().enumerate() {
+            /* key is the only one left below this child, so the whole branch can go.
+             * It is a chain of single children ending in the node holding the value. */
+            if node.map[elem].count == 1 {
+                let mut branch = node.map.remove(elem).unwrap();
+                for elem in &key[i + 1..] {
+                    branch = branch.map.remove(elem).unwrap();
+                }
+                return branch.value;
+            }
+
+            node = node.map.get_mut(elem).unwrap();
+            node.count -= 1;
+        }
+
+        /* key is also a prefix of a longer key within the Trie
+         * which must not be removed accidentally when removing key. */
+        node.value.take()
+    }
+
+    /* Removes all keys from the Trie that share a common prefix.
+     * Returns true if at least one key has been removed. */
+    pub fn remove_pref<I>(&mut self, prefix: I) -> bool
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let prefix: Vec<K> = prefix.into_iter().collect();
+        if prefix.is_empty() {
+            return false;
+        }
+        let removed = self.count_prefix(prefix.iter().cloned());
+        if removed == 0 {
+            return false;
+        }
+
+        let mut node = &mut self.root;
+        node.count -= removed;
+        for elem in &prefix {
+            /* Once a child holds nothing but the keys to be removed, cutting it off removes
+             * them along with all nodes that only led to them. */
+            if node.map[elem].count == removed {
+                node.map.remove(elem);
+                break;
+            }
+
+            node = node.map.get_mut(elem).unwrap();
+            node.count -= removed;
+        }
+        true
+    }
+
+    /* Returns the number of keys that share a common prefix, in time proportional to the prefix's length. */
+    pub fn count_prefix<I>(&self, prefix: I) -> usize
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.find_node(prefix).map_or(0, |node| node.count)
+    }
+
+    /* Whether or not key is present in the Trie. */
+    pub fn contains_key<I>(&self, key: I) -> bool
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /* Whether or not at least one key with the given prefix is present in the Trie. */
+    pub fn contains_pref<I>(&self, prefix: I) -> bool
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.find_node(prefix).is_some()
+    }
+
+    /* Returns an iterator over every stored key that is a prefix of key (including key itself), shortest first.
+     * The prefixes are given by their length, paired with their values. */
+    pub fn prefixes_of<I>(&self, key: I) -> Prefixes<'_, K, V, I::IntoIter>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        Prefixes::new(&self.root, key.into_iter())
+    }
+
+    /* Returns the length of the longest stored key that is a prefix of key, paired with its value. */
+    pub fn longest_prefix_of<I>(&self, key: I) -> Option<(usize, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.prefixes_of(key).last()
+    }
+
+    /* Returns the k-th smallest key (counting from 0) in lexicographic order, paired with its value.
+     * The subtree counts let whole subtrees be skipped, so only the nodes along the key and their
+     * siblings are visited. */
+    pub fn nth(&self, mut k: usize) -> Option<(Vec<K>, &V)> {
+        if k >= self.root.count {
+            return None;
+        }
+
+        let mut key = vec![];
+        let mut node = &self.root;
+        loop {
+            if let Some(value) = &node.value {
+                if k == 0 {
+                    return Some((key, value));
+                }
+                k -= 1;
+            }
+
+            /* k < node.count always holds, so one of the children must contain the k-th key. */
+            for (elem, next_node) in &node.map {
+                if k < next_node.count {
+                    key.push(elem.clone());
+                    node = next_node;
+                    break;
+                }
+                k -= next_node.count;
+            }
+        }
+    }
+
+    /* Returns the number of keys in the Trie that are lexicographically smaller than key,
+     * which is the position key has or would have in as_vec(). */
+    pub fn rank<I>(&self, key: I) -> usize
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut rank = 0;
+        let mut node = &self.root;
+        for elem in key {
+            // Keys that are proper prefixes of key come before it.
+            if node.value.is_some() {
+                rank += 1;
+            }
+            rank += node
+                .map
+                .range(..&elem)
+                .map(|(_, next_node)| next_node.count)
+                .sum::<usize>();
+
+            match node.map.get(&elem) {
+                Some(next_node) => node = next_node,
+                None => break,
+            }
+        }
+        rank
+    }
+
+    /* Returns the greatest key that is less than or equal to key, paired with its value.
+     * key itself does not have to be present in the Trie. */
+    pub fn floor<I>(&self, key: I) -> Option<(Vec<K>, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let key: Vec<K> = key.into_iter().collect();
+        let not_greater = self.rank(key.iter().cloned()) + usize::from(self.contains_key(key));
+        self.nth(not_greater.checked_sub(1)?)
+    }
+
+    /* Returns the smallest key that is greater than or equal to key, paired with its value. */
+    pub fn ceiling<I>(&self, key: I) -> Option<(Vec<K>, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.nth(self.rank(key))
+    }
+
+    /* Returns the smallest key that is strictly greater than key, paired with its value. */
+    pub fn next_after<I>(&self, key: I) -> Option<(Vec<K>, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let key: Vec<K> = key.into_iter().collect();
+        self.nth(self.rank(key.iter().cloned()) + usize::from(self.contains_key(key)))
+    }
+
+    /* Returns the greatest key that is strictly less than key, paired with its value. */
+    pub fn prev_before<I>(&self, key: I) -> Option<(Vec<K>, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.nth(self.rank(key).checked_sub(1)?)
+    }
+
+    /* Returns an iterator lazily yielding all keys present in the Trie, paired with their values.
+     * The keys are yielded in lexicographic order. */
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(vec![], Some(&self.root))
+    }
+
+    /* Like iter(). However, only keys that share a common prefix are yielded. */
+    pub fn iter_prefix<I>(&self, prefix: I) -> Iter<'_, K, V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let prefix: Vec<K> = prefix.into_iter().collect();
+        let node = self.find_node(prefix.iter().cloned());
+        Iter::new(prefix, node)
+    }
+
+    /* Like iter(). However, only keys within the given range are yielded, e.g. trie.range(start..end).
+     * Subtrees entirely outside of the range are never visited. */
+    pub fn range<R: RangeBounds<Vec<K>>>(&self, range: R) -> Range<'_, K, V> {
+        Range::new(
+            &self.root,
+            range.start_bound().map(Vec::as_slice),
+            range.end_bound().cloned(),
+        )
+    }
+
+    /* Builds and returns a vector holding all keys present in the Trie, paired with their values.
+     * The keys are sorted lexicographically. */
+    pub fn as_vec(&self) -> Vec<(Vec<K>, &V)> {
+        self.iter().collect()
+    }
+
+    /* Like as_vec(). However, the returned vector only holds pairs whose keys share a common prefix. */
+    pub fn as_vec_pref<I>(&self, prefix: I) -> Vec<(Vec<K>, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut prefix = prefix.into_iter().peekable();
+        if prefix.peek().is_none() {
+            return vec![];
+        }
+        self.iter_prefix(prefix).collect()
+    }
+}
@@ -0,0 +1,844 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+    ops::Bound,
+    str::Chars,
+};
+
+use crate::{
+    matcher::NO_NODE, radix::RadixNode, Dawg, FrozenTrie, Fst, Matcher, StreamMatcher, TrieBytes,
+    TrieNode,
+};
+
+/* Lazily yields the keys below a node in lexicographic order, paired with references to their values.
+ * Keys are built on an explicit stack instead of recursing, so nothing is collected up front. */
+pub struct Iter<'a, K, V> {
+    /* Each entry holds the length the current key must be truncated to, the element leading
+     * into the node (None for the node the iteration started at) and the node itself. */
+    stack: Vec<(usize, Option<&'a K>, &'a TrieNode<K, V>)>,
+    key: Vec<K>,
+    /* The number of keys yet to be yielded, known from the subtree counts. */
+    remaining: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    /* 'node' is the node pointed to by the last element of 'prefix'. */
+    pub(crate) fn new(prefix: Vec<K>, node: Option<&'a TrieNode<K, V>>) -> Self {
+        let (stack, remaining) = match node {
+            Some(node) => (vec![(prefix.len(), None, node)], node.count),
+            None => (vec![], 0),
+        };
+        Self {
+            stack,
+            key: prefix,
+            remaining,
+        }
+    }
+}
+
+impl<'a, K: Clone, V> Iterator for Iter<'a, K, V> {
+    type Item = (Vec<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, elem, node)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if let Some(elem) = elem {
+                self.key.push(elem.clone());
+            }
+
+            // Children are pushed in reverse so that the smallest one is popped first.
+            let depth = self.key.len();
+            self.stack.extend(
+                node.map
+                    .iter()
+                    .rev()
+                    .map(|(elem, next_node)| (depth, Some(elem), next_node)),
+            );
+
+            if let Some(value) = &node.value {
+                self.remaining -= 1;
+                return Some((self.key.clone(), value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Clone, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/* Like Iter, but takes ownership of the nodes and yields the values themselves. */
+pub struct IntoIter<K, V> {
+    stack: Vec<(usize, Option<K>, TrieNode<K, V>)>,
+    key: Vec<K>,
+    remaining: usize,
+}
+
+impl<K, V> IntoIter<K, V> {
+    pub(crate) fn new(root: TrieNode<K, V>) -> Self {
+        Self {
+            remaining: root.count,
+            stack: vec![(0, None, root)],
+            key: vec![],
+        }
+    }
+}
+
+impl<K: Clone, V> Iterator for IntoIter<K, V> {
+    type Item = (Vec<K>, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, elem, node)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if let Some(elem) = elem {
+                self.key.push(elem);
+            }
+
+            let depth = self.key.len();
+            self.stack.extend(
+                node.map
+                    .into_iter()
+                    .rev()
+                    .map(|(elem, next_node)| (depth, Some(elem), next_node)),
+            );
+
+            if let Some(value) = node.value {
+                self.remaining -= 1;
+                return Some((self.key.clone(), value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Clone, V> ExactSizeIterator for IntoIter<K, V> {}
+
+/* Iterator over the keys and values of a TrieMap. */
+pub struct MapIter<'a, V> {
+    pub(crate) iter: Iter<'a, char, V>,
+}
+
+impl<'a, V> Iterator for MapIter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(key, value)| (key.into_iter().collect(), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<V> ExactSizeIterator for MapIter<'_, V> {}
+
+/* Owning iterator over the keys and values of a TrieMap. */
+pub struct MapIntoIter<V> {
+    pub(crate) iter: IntoIter<char, V>,
+}
+
+impl<V> Iterator for MapIntoIter<V> {
+    type Item = (String, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(key, value)| (key.into_iter().collect(), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<V> ExactSizeIterator for MapIntoIter<V> {}
+
+/* Iterator over the strings of a Trie. */
+pub struct Keys<'a> {
+    pub(crate) iter: MapIter<'a, ()>,
+}
+
+impl Iterator for Keys<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(s, _)| s)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Keys<'_> {}
+
+/* Owning iterator over the strings of a Trie. */
+pub struct IntoKeys {
+    pub(crate) iter: MapIntoIter<()>,
+}
+
+impl Iterator for IntoKeys {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(s, _)| s)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IntoKeys {}
+
+/* Lazily yields the keys within a range in lexicographic order, paired with references to their values. */
+pub struct Range<'a, K, V> {
+    /* Same layout as the stack of Iter. */
+    stack: Vec<(usize, Option<&'a K>, &'a TrieNode<K, V>)>,
+    key: Vec<K>,
+    upper: Bound<Vec<K>>,
+}
+
+impl<'a, K: Ord + Clone, V> Range<'a, K, V> {
+    /* Descends along the lower bound, pushing only the subtrees of siblings greater than it,
+     * so that everything below the range is skipped without being visited. */
+    pub(crate) fn new(root: &'a TrieNode<K, V>, lower: Bound<&[K]>, upper: Bound<Vec<K>>) -> Self {
+        let (lower, included) = match lower {
+            Bound::Included(lower) => (lower, true),
+            Bound::Excluded(lower) => (lower, false),
+            Bound::Unbounded => (&[][..], true),
+        };
+
+        let mut stack = vec![];
+        let mut key = vec![];
+        let mut node = root;
+        for elem in lower {
+            stack.extend(
+                node.map
+                    .range((Bound::Excluded(elem), Bound::Unbounded))
+                    .rev()
+                    .map(|(elem, next_node)| (key.len(), Some(elem), next_node)),
+            );
+            match node.map.get(elem) {
+                Some(next_node) => {
+                    key.push(elem.clone());
+                    node = next_node;
+                }
+                None => return Self { stack, key, upper },
+            }
+        }
+
+        // 'node' is pointed to by the lower bound itself, which only counts if it is included.
+        if included {
+            stack.push((key.len(), None, node));
+        } else {
+            stack.extend(
+                node.map
+                    .iter()
+                    .rev()
+                    .map(|(elem, next_node)| (key.len(), Some(elem), next_node)),
+            );
+        }
+        Self { stack, key, upper }
+    }
+
+    /* Whether or not the current key and all of its extensions lie above the upper bound. */
+    fn past_upper(&self) -> bool {
+        match &self.upper {
+            Bound::Included(upper) => self.key > *upper,
+            Bound::Excluded(upper) => self.key >= *upper,
+            Bound::Unbounded => false,
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V> Iterator for Range<'a, K, V> {
+    type Item = (Vec<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, elem, node)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if let Some(elem) = elem {
+                self.key.push(elem.clone());
+            }
+            // Keys are visited in order, so nothing in range is left.
+            if self.past_upper() {
+                self.stack.clear();
+                return None;
+            }
+
+            let depth = self.key.len();
+            self.stack.extend(
+                node.map
+                    .iter()
+                    .rev()
+                    .map(|(elem, next_node)| (depth, Some(elem), next_node)),
+            );
+
+            if let Some(value) = &node.value {
+                return Some((self.key.clone(), value));
+            }
+        }
+        None
+    }
+}
+
+/* Iterator over the keys and values of a TrieMap within a range. */
+pub struct MapRange<'a, V> {
+    pub(crate) iter: Range<'a, char, V>,
+}
+
+impl<'a, V> Iterator for MapRange<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(key, value)| (key.into_iter().collect(), value))
+    }
+}
+
+/* Iterator over the strings of a Trie within a range. */
+pub struct RangeKeys<'a> {
+    pub(crate) iter: MapRange<'a, ()>,
+}
+
+impl Iterator for RangeKeys<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(s, _)| s)
+    }
+}
+
+/* Iterator over the strings of a RadixTrie, in lexicographic order. */
+pub struct RadixKeys<'a> {
+    /* Each entry holds the length the current key must be truncated to before the node's label is appended. */
+    stack: Vec<(usize, &'a RadixNode)>,
+    key: String,
+    remaining: Option<usize>,
+}
+
+impl<'a> RadixKeys<'a> {
+    /* 'prefix' is the key leading up to (but not including the label of) 'node'. */
+    pub(crate) fn new(
+        prefix: String,
+        node: Option<&'a RadixNode>,
+        remaining: Option<usize>,
+    ) -> Self {
+        let stack = match node {
+            Some(node) => vec![(prefix.len(), node)],
+            None => vec![],
+        };
+        Self {
+            stack,
+            key: prefix,
+            remaining,
+        }
+    }
+}
+
+impl Iterator for RadixKeys<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, node)) = self.stack.pop() {
+            self.key.truncate(depth);
+            self.key.push_str(&node.label);
+
+            let depth = self.key.len();
+            self.stack
+                .extend(node.children.values().rev().map(|child| (depth, child)));
+
+            if node.end_of_word {
+                if let Some(remaining) = &mut self.remaining {
+                    *remaining -= 1;
+                }
+                return Some(self.key.clone());
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None if self.stack.is_empty() => (0, Some(0)),
+            None => (0, None),
+        }
+    }
+}
+
+/* Lazily yields every stored key that is a prefix of a given key, shortest first.
+ * Each item holds the length of the prefix (in elements) and a reference to its value. */
+pub struct Prefixes<'a, K, V, I> {
+    node: Option<&'a TrieNode<K, V>>,
+    key: I,
+    len: usize,
+}
+
+impl<'a, K, V, I> Prefixes<'a, K, V, I> {
+    pub(crate) fn new(root: &'a TrieNode<K, V>, key: I) -> Self {
+        Self {
+            node: Some(root),
+            key,
+            len: 0,
+        }
+    }
+}
+
+impl<'a, K: Ord, V, I: Iterator<Item = K>> Iterator for Prefixes<'a, K, V, I> {
+    type Item = (usize, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.node {
+            self.node = self.key.next().and_then(|elem| node.map.get(&elem));
+            if let Some(next_node) = self.node {
+                self.len += 1;
+                if let Some(value) = &next_node.value {
+                    return Some((self.len, value));
+                }
+            }
+        }
+        None
+    }
+}
+
+/* Iterator over the keys of a TrieMap that are prefixes of a string s, shortest first.
+ * The keys are yielded as slices of s. */
+pub struct MapPrefixes<'a, 's, V> {
+    pub(crate) iter: Prefixes<'a, char, V, Chars<'s>>,
+    pub(crate) s: &'s str,
+    /* The last yielded prefix, both in chars and in bytes. */
+    pub(crate) char_len: usize,
+    pub(crate) byte_len: usize,
+}
+
+impl<'a, 's, V> Iterator for MapPrefixes<'a, 's, V> {
+    type Item = (&'s str, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (len, value) = self.iter.next()?;
+        self.byte_len += self.s[self.byte_len..]
+            .chars()
+            .take(len - self.char_len)
+            .map(char::len_utf8)
+            .sum::<usize>();
+        self.char_len = len;
+        Some((&self.s[..self.byte_len], value))
+    }
+}
+
+/* Iterator over the strings of a Trie that are prefixes of a string s, shortest first. */
+pub struct PrefixKeys<'a, 's> {
+    pub(crate) iter: MapPrefixes<'a, 's, ()>,
+}
+
+impl<'s> Iterator for PrefixKeys<'_, 's> {
+    type Item = &'s str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(s, _)| s)
+    }
+}
+
+/* Iterator over the strings of a TrieBytes, in lexicographic order. */
+pub struct BytesKeys<'a> {
+    trie: TrieBytes<'a>,
+    /* Each entry holds the length (in chars) the current key must be truncated to and the index of a node.
+     * The node the iteration started at is marked by not pushing its label. */
+    stack: Vec<(usize, u32, bool)>,
+    key: Vec<char>,
+}
+
+impl<'a> BytesKeys<'a> {
+    pub(crate) fn new(trie: TrieBytes<'a>, prefix: String, node: Option<u32>) -> Self {
+        let key: Vec<char> = prefix.chars().collect();
+        let stack = match node {
+            Some(node) => vec![(key.len(), node, false)],
+            None => vec![],
+        };
+        Self { trie, stack, key }
+    }
+}
+
+impl Iterator for BytesKeys<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, node, push_label)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if push_label {
+                self.key.push(self.trie.char(node));
+            }
+
+            let (first_child, child_count, terminal) = self.trie.node(node);
+            let depth = self.key.len();
+            self.stack.extend(
+                (first_child..first_child + child_count)
+                    .rev()
+                    .map(|child| (depth, child, true)),
+            );
+
+            if terminal {
+                return Some(self.key.iter().collect());
+            }
+        }
+        None
+    }
+}
+
+/* Iterator over the strings of a FrozenTrie, in lexicographic order. Works like BytesKeys. */
+pub struct FrozenKeys<'a> {
+    trie: &'a FrozenTrie,
+    stack: Vec<(usize, u32, bool)>,
+    key: Vec<char>,
+}
+
+impl<'a> FrozenKeys<'a> {
+    pub(crate) fn new(trie: &'a FrozenTrie, prefix: String, node: Option<u32>) -> Self {
+        let key: Vec<char> = prefix.chars().collect();
+        let stack = match node {
+            Some(node) => vec![(key.len(), node, false)],
+            None => vec![],
+        };
+        Self { trie, stack, key }
+    }
+}
+
+impl Iterator for FrozenKeys<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, node, push_label)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if push_label {
+                self.key.push(self.trie.label(node));
+            }
+
+            let (start, end) = self.trie.children(node);
+            let depth = self.key.len();
+            self.stack
+                .extend((start..end).rev().map(|child| (depth, child, true)));
+
+            if self.trie.is_terminal(node) {
+                return Some(self.key.iter().collect());
+            }
+        }
+        None
+    }
+}
+
+/* Iterator over the strings of a Dawg, in lexicographic order. Works like BytesKeys. */
+pub struct DawgKeys<'a> {
+    dawg: &'a Dawg,
+    stack: Vec<(usize, u32, Option<char>)>,
+    key: Vec<char>,
+}
+
+impl<'a> DawgKeys<'a> {
+    pub(crate) fn new(dawg: &'a Dawg, prefix: String, node: Option<u32>) -> Self {
+        let key: Vec<char> = prefix.chars().collect();
+        let stack = match node {
+            Some(node) => vec![(key.len(), node, None)],
+            None => vec![],
+        };
+        Self { dawg, stack, key }
+    }
+}
+
+impl Iterator for DawgKeys<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, node, label)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if let Some(label) = label {
+                self.key.push(label);
+            }
+
+            let node = self.dawg.node(node);
+            let depth = self.key.len();
+            self.stack.extend(
+                node.edges
+                    .iter()
+                    .rev()
+                    .map(|&(label, child)| (depth, child, Some(label))),
+            );
+
+            if node.terminal {
+                return Some(self.key.iter().collect());
+            }
+        }
+        None
+    }
+}
+
+/* Stream over the keys of an Fst in lexicographic order, paired with their values,
+ * optionally restricted to a range of keys. */
+pub struct FstStream<'a> {
+    fst: &'a Fst,
+    /* Each entry holds the length the current key must be truncated to, the node, the label leading
+     * into it (None for the node the stream started at) and the sum of the outputs up to the node. */
+    stack: Vec<(usize, u32, Option<char>, u64)>,
+    key: Vec<char>,
+    lower: Bound<Vec<char>>,
+    upper: Bound<Vec<char>>,
+}
+
+impl<'a> FstStream<'a> {
+    pub(crate) fn new(
+        fst: &'a Fst,
+        prefix: Vec<char>,
+        node: Option<(u32, u64)>,
+        lower: Bound<Vec<char>>,
+        upper: Bound<Vec<char>>,
+    ) -> Self {
+        let stack = match node {
+            Some((node, output)) => vec![(prefix.len(), node, None, output)],
+            None => vec![],
+        };
+        Self {
+            fst,
+            stack,
+            key: prefix,
+            lower,
+            upper,
+        }
+    }
+
+    /* Whether or not some extension of 'key' can still be at or above the lower bound. */
+    fn may_reach_lower(&self, key: &[char]) -> bool {
+        match &self.lower {
+            Bound::Included(lower) | Bound::Excluded(lower) => {
+                key >= lower.as_slice() || lower.starts_with(key)
+            }
+            Bound::Unbounded => true,
+        }
+    }
+
+    /* Whether or not every extension of the current key is above the upper bound. */
+    fn past_upper(&self) -> bool {
+        match &self.upper {
+            Bound::Included(upper) | Bound::Excluded(upper) => {
+                self.key.as_slice() > upper.as_slice() && !upper.starts_with(&self.key)
+            }
+            Bound::Unbounded => false,
+        }
+    }
+
+    fn in_range(&self) -> bool {
+        let key = self.key.as_slice();
+        let above_lower = match &self.lower {
+            Bound::Included(lower) => key >= lower.as_slice(),
+            Bound::Excluded(lower) => key > lower.as_slice(),
+            Bound::Unbounded => true,
+        };
+        let below_upper = match &self.upper {
+            Bound::Included(upper) => key <= upper.as_slice(),
+            Bound::Excluded(upper) => key < upper.as_slice(),
+            Bound::Unbounded => true,
+        };
+        above_lower && below_upper
+    }
+}
+
+impl Iterator for FstStream<'_> {
+    type Item = (String, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, node, label, output)) = self.stack.pop() {
+            self.key.truncate(depth);
+            if let Some(label) = label {
+                self.key.push(label);
+            }
+            // Keys only grow from here on, so nothing in range is left.
+            if self.past_upper() {
+                self.stack.clear();
+                return None;
+            }
+
+            let node = self.fst.node(node);
+            let depth = self.key.len();
+            for edge in node.edges.iter().rev() {
+                self.key.push(edge.label);
+                if self.may_reach_lower(&self.key) {
+                    self.stack
+                        .push((depth, edge.target, Some(edge.label), output + edge.output));
+                }
+                self.key.pop();
+            }
+
+            if node.is_final && self.in_range() {
+                return Some((self.key.iter().collect(), output + node.final_output));
+            }
+        }
+        None
+    }
+}
+
+/* Lazily yields the leftmost-longest matches of a Matcher in a text, see Matcher::find_iter(). */
+pub struct Matches<'m, 't> {
+    matcher: &'m Matcher,
+    text: &'t str,
+    /* The byte offset of the next char to consume. */
+    pos: usize,
+    state: u32,
+    /* The leftmost-longest match found so far that has not been yielded yet. */
+    candidate: Option<(usize, usize)>,
+}
+
+impl<'m, 't> Matches<'m, 't> {
+    pub(crate) fn new(matcher: &'m Matcher, text: &'t str) -> Self {
+        Self {
+            matcher,
+            text,
+            pos: 0,
+            state: 0,
+            candidate: None,
+        }
+    }
+
+    /* Yields the candidate and restarts the search at its end, so that matches cannot overlap. */
+    fn take_candidate(&mut self) -> Option<(usize, usize, &'t str)> {
+        let (start, end) = self.candidate.take()?;
+        self.pos = end;
+        self.state = 0;
+        Some((start, end, &self.text[start..end]))
+    }
+}
+
+impl<'t> Iterator for Matches<'_, 't> {
+    type Item = (usize, usize, &'t str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let ch = match self.text[self.pos..].chars().next() {
+                Some(ch) => ch,
+                None => return self.take_candidate(),
+            };
+            self.pos += ch.len_utf8();
+            self.state = self.matcher.next_state(self.state, ch);
+
+            // The longest match ending here starts first, any shorter ones cannot win.
+            let node = self.matcher.first_output(self.state);
+            if node != NO_NODE {
+                let start = self.pos - self.matcher.byte_len(node);
+                if self
+                    .candidate
+                    .is_none_or(|(first_start, _)| start <= first_start)
+                {
+                    self.candidate = Some((start, self.pos));
+                }
+            }
+
+            /* Every match still to come is a suffix of the text read so far extended further,
+             * so it starts within the string of the current state. Once that begins after the
+             * candidate, nothing can start earlier or at the same offset and be longer. */
+            if let Some((start, _)) = self.candidate {
+                if self.pos - self.matcher.byte_len(self.state) > start {
+                    return self.take_candidate();
+                }
+            }
+        }
+    }
+}
+
+/* Lazily yields all matches of a Matcher in a text, see Matcher::find_overlapping_iter(). */
+pub struct OverlappingMatches<'m, 't> {
+    matcher: &'m Matcher,
+    text: &'t str,
+    pos: usize,
+    state: u32,
+    /* The next match ending at pos that has not been yielded yet, or NO_NODE. */
+    pending: u32,
+}
+
+impl<'m, 't> OverlappingMatches<'m, 't> {
+    pub(crate) fn new(matcher: &'m Matcher, text: &'t str) -> Self {
+        Self {
+            matcher,
+            text,
+            pos: 0,
+            state: 0,
+            pending: NO_NODE,
+        }
+    }
+}
+
+impl<'t> Iterator for OverlappingMatches<'_, 't> {
+    type Item = (usize, usize, &'t str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending == NO_NODE {
+            let ch = self.text[self.pos..].chars().next()?;
+            self.pos += ch.len_utf8();
+            self.state = self.matcher.next_state(self.state, ch);
+            self.pending = self.matcher.first_output(self.state);
+        }
+
+        let node = self.pending;
+        self.pending = self.matcher.next_output(node);
+        let start = self.pos - self.matcher.byte_len(node);
+        Some((start, self.pos, &self.text[start..self.pos]))
+    }
+}
+
+/* Lazily yields the matches of a StreamMatcher in the data of a reader, see Matcher::find_read(). */
+pub struct ReadMatches<'m, R> {
+    stream: StreamMatcher<'m>,
+    reader: R,
+    chunk: Vec<u8>,
+    /* Matches found in the chunks read so far that have not been yielded yet. */
+    matches: VecDeque<(usize, usize, String)>,
+    done: bool,
+}
+
+impl<'m, R: Read> ReadMatches<'m, R> {
+    pub(crate) fn new(stream: StreamMatcher<'m>, reader: R) -> Self {
+        Self {
+            stream,
+            reader,
+            chunk: vec![0; 8192],
+            matches: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: Read> Iterator for ReadMatches<'_, R> {
+    type Item = io::Result<(usize, usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.matches.pop_front() {
+                return Some(Ok(found));
+            }
+            if self.done {
+                return None;
+            }
+
+            match self.reader.read(&mut self.chunk) {
+                Ok(0) => {
+                    self.done = true;
+                    self.matches.extend(self.stream.finish());
+                }
+                Ok(len) => self.matches.extend(self.stream.feed(&self.chunk[..len])),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
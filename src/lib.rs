@@ -1,297 +1,220 @@
-use std::{cell::Cell, collections::HashMap};
-
+mod automaton;
+mod binary;
+mod dawg;
+mod entry;
+mod frozen;
+mod fst;
+mod fuzzy;
+mod generic;
+pub mod iter;
+mod map;
+mod matcher;
+mod pattern;
+mod radix;
+#[cfg(feature = "serde")]
+mod serialization;
+mod set;
+mod stream;
 mod traits;
-
-#[derive(Debug)]
-pub struct TrieNode {
-    map: HashMap<char, TrieNode>,
-    end_of_word: bool,
-}
-
-#[derive(Debug)]
+mod weighted;
+
+pub use automaton::Automaton;
+pub use binary::{FormatError, TrieBytes};
+pub use dawg::{Dawg, DawgBuilder};
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use frozen::FrozenTrie;
+pub use fst::{Fst, FstBuilder};
+pub use generic::{GenericTrie, TrieNode};
+pub use map::TrieMap;
+pub use matcher::Matcher;
+pub use pattern::Pattern;
+pub use radix::{RadixNode, RadixTrie};
+pub use stream::StreamMatcher;
+pub use weighted::WeightedTrie;
+
+use std::ops::RangeBounds;
+
+use iter::{Keys, PrefixKeys, RangeKeys};
+
+/* A set of strings, backed by a TrieMap without values. */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Trie {
-    root: TrieNode,
-    stored_size: Cell<Option<usize>>,
+    pub(crate) map: TrieMap<()>,
 }
 
 impl Trie {
     pub fn new() -> Self {
-        let root = TrieNode {
-            map: HashMap::new(),
-            end_of_word: false,
-        };
-
         Self {
-            root,
-            stored_size: Cell::new(Some(0)),
+            map: TrieMap::new(),
         }
     }
 
-    /* Returns the number of strings in the Trie. If unknown, all strings are counted first and the size is stored. */
+    /* Returns the number of strings in the Trie. */
     pub fn size(&self) -> usize {
-        if let Some(size) = self.stored_size.get() {
-            return size;
-        }
-
-        let mut stack: Vec<&TrieNode> = self.root.map.values().collect();
-        let mut size = 0;
-
-        while let Some(node) = stack.pop() {
-            if node.end_of_word {
-                size += 1;
-            }
-
-            node.map.values().for_each(|x| {
-                if x.map.is_empty() {
-                    size += 1;
-                } else {
-                    stack.push(x);
-                }
-            });
-        }
-
-        self.stored_size.set(Some(size));
-        size
-    }
-
-    /* Increments (incr = true) or decrements (incr = false) the stored size by 1. If stored size is None, nothing happens. */
-    fn edit_size(&self, incr: bool) {
-        let mut size;
-        if let Some(s) = self.stored_size.get() {
-            size = s;
-        } else {
-            return;
-        }
-
-        if incr {
-            size += 1;
-        } else if size != 0 {
-            size -= 1;
-        } else {
-            return;
-        }
-
-        self.stored_size.set(Some(size));
+        self.map.size()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.root.map.is_empty()
+        self.map.is_empty()
     }
 
     pub fn clear(&mut self) {
-        self.root.map.clear();
-        self.stored_size.set(Some(0));
+        self.map.clear();
     }
 
     /* Ensures that s is present in the Trie.
      * Returns true only if s is not present in the Trie when insert() is called. */
     pub fn insert(&mut self, s: &str) -> bool {
-        if s.is_empty() {
-            return false;
-        }
-
-        let mut node = &mut self.root;
-        for ch in s.chars() {
-            node = node.map.entry(ch).or_insert(TrieNode {
-                map: HashMap::new(),
-                end_of_word: false,
-            });
-        }
-
-        let mut is_new = false;
-        if !node.end_of_word {
-            is_new = true;
-            node.end_of_word = true;
-        }
-
-        if is_new {
-            self.edit_size(true);
-        }
-        is_new
+        !s.is_empty() && self.map.insert(s, ()).is_none()
     }
 
     /* Removes an entire string s from the Trie.
      * Returns true if and only if s was present up until removal. */
     pub fn remove(&mut self, s: &str) -> bool {
-        if s.is_empty() {
-            return false;
-        }
+        self.map.remove(s).is_some()
+    }
 
-        /* Holds the index at which we can safely remove s without
-         * unintentionally removing other strings with the same prefix as s. */
-        let mut remove_index = None;
-        let mut node = &mut self.root;
-        for (i, ch) in s.chars().enumerate() {
-            if node.end_of_word {
-                /* Reset the index here to ensure we don't remove the substring of s which
-                 * seems to be present in the Trie. */
-                remove_index = None;
-            }
-
-            if let Some(next_node) = node.map.get_mut(&ch) {
-                node = next_node;
-                if node.map.len() > 1 {
-                    remove_index = None;
-                } else if remove_index.is_none() {
-                    remove_index = Some(i);
-                }
-            } else {
-                return false;
-            }
-        }
+    /* Removes all strings from the Trie that share a common prefix s.
+     * Returns true if at least one string has been removed. */
+    pub fn remove_pref(&mut self, s: &str) -> bool {
+        self.map.remove_pref(s)
+    }
 
-        // s is not present in the Trie.
-        if !node.end_of_word {
-            return false;
-        }
+    /* Whether or not s is present in the Trie. */
+    pub fn contains(&self, s: &str) -> bool {
+        self.map.contains_key(s)
+    }
 
-        /* s is present in the Trie, but it also is a substring of a longer string within
-         * the Trie which must not be removed accidentally when removing s. */
-        if !node.map.is_empty() {
-            node.end_of_word = false;
-            self.edit_size(false);
-            return true;
-        }
+    /* Whether or not at least one string with a prefix s is present in the Trie. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.map.contains_pref(s)
+    }
 
-        // remove_index will not be None at this point.
-        let remove_index = remove_index.unwrap();
-        node = &mut self.root;
-        for (i, ch) in s.chars().enumerate() {
-            if i == remove_index {
-                node.map.remove(&ch);
-                break;
-            }
-            node = node.map.get_mut(&ch).unwrap();
+    /* Returns the number of strings with a prefix s, in time proportional to the length of s. */
+    pub fn count_prefix(&self, s: &str) -> usize {
+        self.map.count_prefix(s)
+    }
+
+    /* Returns an iterator over every string in the Trie that is a prefix of s (including s itself), shortest first.
+     * The strings are yielded as slices of s. */
+    pub fn prefixes_of<'s>(&self, s: &'s str) -> PrefixKeys<'_, 's> {
+        PrefixKeys {
+            iter: self.map.prefixes_of(s),
         }
+    }
 
-        self.edit_size(false);
-        true
+    /* Returns the longest string in the Trie that is a prefix of s, as a slice of s. */
+    pub fn longest_prefix_of<'s>(&self, s: &'s str) -> Option<&'s str> {
+        self.map.longest_prefix_of(s).map(|(prefix, _)| prefix)
     }
 
-    /* Removes all strings from the Trie that share a common prefix s.
-     * Returns true if at least one string has been removed.
-     * In structure similar to remove(), so refer to its comments. */
-    pub fn remove_pref(&mut self, s: &str) -> bool {
-        if s.is_empty() {
-            return false;
-        }
-        if s.len() == 1 {
-            let ch = s.chars().next().unwrap();
-            self.stored_size.set(None);
-            return self.root.map.remove(&ch).is_some();
-        }
+    /* Returns the k-th smallest string (counting from 0) in lexicographic order,
+     * e.g. trie.nth(page * page_len) is the first string on a page. */
+    pub fn nth(&self, k: usize) -> Option<String> {
+        self.map.nth(k).map(|(s, _)| s)
+    }
 
-        let mut remove_index = None;
-        let mut node = &mut self.root;
-        for (i, ch) in s.chars().enumerate() {
-            if let Some(next_node) = node.map.get_mut(&ch) {
-                node = next_node;
-
-                if node.map.len() > 1 && i != s.len() - 1 {
-                    remove_index = None;
-                } else if remove_index.is_none() {
-                    remove_index = Some(i);
-                }
-            } else {
-                return false;
-            }
-        }
+    /* Returns the number of strings in the Trie that are lexicographically smaller than s. */
+    pub fn rank(&self, s: &str) -> usize {
+        self.map.rank(s)
+    }
 
-        node = &mut self.root;
-        let remove_index = remove_index.unwrap();
-        for (i, ch) in s.chars().enumerate() {
-            if i == remove_index {
-                node.map.remove(&ch);
-                break;
-            }
-            node = node.map.get_mut(&ch).unwrap();
-        }
+    /* Returns the greatest string in the Trie that is less than or equal to s.
+     * s itself does not have to be present in the Trie. */
+    pub fn floor(&self, s: &str) -> Option<String> {
+        self.map.floor(s).map(|(s, _)| s)
+    }
 
-        self.stored_size.set(None);
-        true
+    /* Returns the smallest string in the Trie that is greater than or equal to s. */
+    pub fn ceiling(&self, s: &str) -> Option<String> {
+        self.map.ceiling(s).map(|(s, _)| s)
     }
 
-    /* Whether or not s is present in the Trie. */
-    pub fn contains(&self, s: &str) -> bool {
-        let mut node = &self.root;
-        for ch in s.chars() {
-            if let Some(next_node) = node.map.get(&ch) {
-                node = next_node;
-            } else {
-                return false;
-            }
-        }
-        node.end_of_word
+    /* Returns the smallest string in the Trie that is strictly greater than s. */
+    pub fn next_after(&self, s: &str) -> Option<String> {
+        self.map.next_after(s).map(|(s, _)| s)
     }
 
-    /* Whether or not at least one string with a prefix s is present in the Trie. */
-    pub fn contains_pref(&self, s: &str) -> bool {
-        let mut node = &self.root;
-        for ch in s.chars() {
-            if let Some(next_node) = node.map.get(&ch) {
-                node = next_node;
-            } else {
-                return false;
-            }
-        }
+    /* Returns the greatest string in the Trie that is strictly less than s. */
+    pub fn prev_before(&self, s: &str) -> Option<String> {
+        self.map.prev_before(s).map(|(s, _)| s)
+    }
 
-        true
+    /* Returns every string within Levenshtein distance max_distance of s, paired with its distance.
+     * The strings are sorted lexicographically. */
+    pub fn fuzzy_search(&self, s: &str, max_distance: usize) -> Vec<(String, usize)> {
+        without_values(self.map.fuzzy_search(s, max_distance))
     }
 
-    /* Builds and returns a vector holding all strings present in the Trie.
-     * The vector is not sorted, but the strings are grouped by prefix. */
-    pub fn as_vec(&self) -> Vec<String> {
-        let mut strings = vec![];
+    /* Like fuzzy_search(), but swapping two adjacent characters counts as a single edit. */
+    pub fn fuzzy_search_with_transpositions(
+        &self,
+        s: &str,
+        max_distance: usize,
+    ) -> Vec<(String, usize)> {
+        without_values(self.map.fuzzy_search_with_transpositions(s, max_distance))
+    }
 
-        self.walk_nodes(&mut vec![], &self.root, &mut strings);
+    /* Returns every string matching the glob pattern, in lexicographic order.
+     * Refer to TrieMap::matches() for the supported syntax. */
+    pub fn matches(&self, pattern: &str) -> Vec<String> {
+        self.map
+            .matches(pattern)
+            .into_iter()
+            .map(|(s, _)| s)
+            .collect()
+    }
 
-        strings
+    /* Returns every string accepted by the automaton, in lexicographic order. */
+    pub fn search<A: Automaton>(&self, automaton: A) -> Vec<String> {
+        self.map
+            .search(automaton)
+            .into_iter()
+            .map(|(s, _)| s)
+            .collect()
     }
 
-    /* Like as_vec(). However, the returned vector only holds strings that share a common prefix s. */
-    pub fn as_vec_pref(&self, s: &str) -> Vec<String> {
-        if s.is_empty() {
-            return vec![];
-        }
-        let mut strings = vec![];
-        let mut node = &self.root;
-        for ch in s.chars() {
-            if let Some(next_node) = node.map.get(&ch) {
-                node = next_node;
-            } else {
-                return vec![];
-            }
+    /* Returns an iterator lazily yielding all strings present in the Trie.
+     * The strings are yielded in lexicographic order. */
+    pub fn iter(&self) -> Keys<'_> {
+        Keys {
+            iter: self.map.iter(),
         }
+    }
 
-        //walk_nodes() does not consider that the prefix itself might be a string present in the Trie.
-        if node.end_of_word {
-            strings.push(s.into())
+    /* Like iter(). However, only strings that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> Keys<'_> {
+        Keys {
+            iter: self.map.iter_prefix(s),
         }
+    }
 
-        /* 'node' is the node pointed to by the last character of s. tmp_string is initialized
-         * with the characters of s. This way, walk_nodes will not pop any characters within the prefix. */
-        self.walk_nodes(&mut s.chars().collect(), node, &mut strings);
+    /* Like iter(). However, only strings within the given range are yielded in lexicographic order,
+     * e.g. trie.range("mar".."mat") for all words from "mar" up to (but excluding) "mat". */
+    pub fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> RangeKeys<'_> {
+        RangeKeys {
+            iter: self.map.range(range),
+        }
+    }
 
-        strings
+    /* Builds and returns a vector holding all strings present in the Trie.
+     * The strings are sorted lexicographically. */
+    pub fn as_vec(&self) -> Vec<String> {
+        self.iter().collect()
     }
 
-    /* Recursively walks all the child nodes of 'node' to construct the strings formed by their characters,
-     * while feeding the complete strings into all_strings. */
-    fn walk_nodes(
-        &self,
-        tmp_string: &mut Vec<char>,
-        node: &TrieNode,
-        all_strings: &mut Vec<String>,
-    ) {
-        for (ch, next_node) in node.map.iter() {
-            tmp_string.push(*ch);
-            if next_node.end_of_word {
-                all_strings.push(tmp_string.iter().collect());
-            }
-            if !next_node.map.is_empty() {
-                self.walk_nodes(tmp_string, next_node, all_strings);
-            }
-            tmp_string.pop();
+    /* Like as_vec(). However, the returned vector only holds strings that share a common prefix s. */
+    pub fn as_vec_pref(&self, s: &str) -> Vec<String> {
+        if s.is_empty() {
+            return vec![];
         }
+        self.iter_prefix(s).collect()
     }
 }
+
+fn without_values(matches: Vec<(String, &(), usize)>) -> Vec<(String, usize)> {
+    matches
+        .into_iter()
+        .map(|(s, _, distance)| (s, distance))
+        .collect()
+}
@@ -0,0 +1,218 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::{
+    iter::{MapIter, MapPrefixes, MapRange},
+    Automaton, GenericTrie,
+};
+
+/* A Trie that associates a value with every stored string. */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrieMap<V> {
+    pub(crate) trie: GenericTrie<char, V>,
+}
+
+impl<V> TrieMap<V> {
+    pub fn new() -> Self {
+        Self {
+            trie: GenericTrie::new(),
+        }
+    }
+
+    /* Returns the number of keys in the TrieMap. */
+    pub fn size(&self) -> usize {
+        self.trie.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trie.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.trie.clear();
+    }
+
+    /* Associates value with the key s. Empty keys are not stored.
+     * Returns the value previously associated with s, if any. */
+    pub fn insert(&mut self, s: &str, value: V) -> Option<V> {
+        self.trie.insert(s.chars(), value)
+    }
+
+    /* Returns a reference to the value associated with s. */
+    pub fn get(&self, s: &str) -> Option<&V> {
+        self.trie.get(s.chars())
+    }
+
+    /* Returns a mutable reference to the value associated with s. */
+    pub fn get_mut(&mut self, s: &str) -> Option<&mut V> {
+        self.trie.get_mut(s.chars())
+    }
+
+    /* Removes the key s from the TrieMap.
+     * Returns the value associated with s up until removal. */
+    pub fn remove(&mut self, s: &str) -> Option<V> {
+        self.trie.remove(s.chars())
+    }
+
+    /* Removes all keys from the TrieMap that share a common prefix s.
+     * Returns true if at least one key has been removed. */
+    pub fn remove_pref(&mut self, s: &str) -> bool {
+        self.trie.remove_pref(s.chars())
+    }
+
+    /* Whether or not the key s is present in the TrieMap. */
+    pub fn contains_key(&self, s: &str) -> bool {
+        self.trie.contains_key(s.chars())
+    }
+
+    /* Whether or not at least one key with a prefix s is present in the TrieMap. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.trie.contains_pref(s.chars())
+    }
+
+    /* Returns the number of keys with a prefix s, in time proportional to the length of s. */
+    pub fn count_prefix(&self, s: &str) -> usize {
+        self.trie.count_prefix(s.chars())
+    }
+
+    /* Returns an iterator over every key that is a prefix of s (including s itself), shortest first.
+     * The keys are yielded as slices of s, paired with their values. */
+    pub fn prefixes_of<'s>(&self, s: &'s str) -> MapPrefixes<'_, 's, V> {
+        MapPrefixes {
+            iter: self.trie.prefixes_of(s.chars()),
+            s,
+            char_len: 0,
+            byte_len: 0,
+        }
+    }
+
+    /* Returns the longest key that is a prefix of s as a slice of s, paired with its value. */
+    pub fn longest_prefix_of<'s>(&self, s: &'s str) -> Option<(&'s str, &V)> {
+        self.prefixes_of(s).last()
+    }
+
+    /* Returns the k-th smallest key (counting from 0) in lexicographic order, paired with its value. */
+    pub fn nth(&self, k: usize) -> Option<(String, &V)> {
+        self.trie.nth(k).map(to_string)
+    }
+
+    /* Returns the number of keys in the TrieMap that are lexicographically smaller than s. */
+    pub fn rank(&self, s: &str) -> usize {
+        self.trie.rank(s.chars())
+    }
+
+    /* Returns the greatest key that is less than or equal to s, paired with its value.
+     * s itself does not have to be present in the TrieMap. */
+    pub fn floor(&self, s: &str) -> Option<(String, &V)> {
+        self.trie.floor(s.chars()).map(to_string)
+    }
+
+    /* Returns the smallest key that is greater than or equal to s, paired with its value. */
+    pub fn ceiling(&self, s: &str) -> Option<(String, &V)> {
+        self.trie.ceiling(s.chars()).map(to_string)
+    }
+
+    /* Returns the smallest key that is strictly greater than s, paired with its value. */
+    pub fn next_after(&self, s: &str) -> Option<(String, &V)> {
+        self.trie.next_after(s.chars()).map(to_string)
+    }
+
+    /* Returns the greatest key that is strictly less than s, paired with its value. */
+    pub fn prev_before(&self, s: &str) -> Option<(String, &V)> {
+        self.trie.prev_before(s.chars()).map(to_string)
+    }
+
+    /* Returns every key within Levenshtein distance max_distance of s, paired with its value
+     * and its distance, in lexicographic order. */
+    pub fn fuzzy_search(&self, s: &str, max_distance: usize) -> Vec<(String, &V, usize)> {
+        to_strings(self.trie.fuzzy_search(s.chars(), max_distance))
+    }
+
+    /* Like fuzzy_search(), but swapping two adjacent characters counts as a single edit. */
+    pub fn fuzzy_search_with_transpositions(
+        &self,
+        s: &str,
+        max_distance: usize,
+    ) -> Vec<(String, &V, usize)> {
+        to_strings(
+            self.trie
+                .fuzzy_search_with_transpositions(s.chars(), max_distance),
+        )
+    }
+
+    /* Returns every key matching the glob pattern, paired with its value, in lexicographic order.
+     * The pattern supports ? (any single char), * (any sequence of chars) and character classes
+     * like [a-z], [abc] or [!a-z]. A backslash escapes the next char. */
+    pub fn matches(&self, pattern: &str) -> Vec<(String, &V)> {
+        self.trie
+            .matches(pattern)
+            .into_iter()
+            .map(|(key, value)| (key.into_iter().collect(), value))
+            .collect()
+    }
+
+    /* Returns every key accepted by the automaton, paired with its value, in lexicographic order. */
+    pub fn search<A: Automaton>(&self, automaton: A) -> Vec<(String, &V)> {
+        self.trie
+            .search(automaton)
+            .into_iter()
+            .map(|(key, value)| (key.into_iter().collect(), value))
+            .collect()
+    }
+
+    /* Returns an iterator lazily yielding all keys present in the TrieMap, paired with their values.
+     * The keys are yielded in lexicographic order. */
+    pub fn iter(&self) -> MapIter<'_, V> {
+        MapIter {
+            iter: self.trie.iter(),
+        }
+    }
+
+    /* Like iter(). However, only keys that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> MapIter<'_, V> {
+        MapIter {
+            iter: self.trie.iter_prefix(s.chars()),
+        }
+    }
+
+    /* Like iter(). However, only keys within the given range are yielded, e.g. map.range("mar".."mat"). */
+    pub fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> MapRange<'_, V> {
+        let to_chars = |bound: Bound<&&str>| -> Bound<Vec<char>> {
+            match bound {
+                Bound::Included(s) => Bound::Included(s.chars().collect()),
+                Bound::Excluded(s) => Bound::Excluded(s.chars().collect()),
+                Bound::Unbounded => Bound::Unbounded,
+            }
+        };
+        MapRange {
+            iter: self
+                .trie
+                .range((to_chars(range.start_bound()), to_chars(range.end_bound()))),
+        }
+    }
+
+    /* Builds and returns a vector holding all keys present in the TrieMap, paired with their values.
+     * The keys are sorted lexicographically. */
+    pub fn as_vec(&self) -> Vec<(String, &V)> {
+        self.iter().collect()
+    }
+
+    /* Like as_vec(). However, the returned vector only holds pairs whose keys share a common prefix s. */
+    pub fn as_vec_pref(&self, s: &str) -> Vec<(String, &V)> {
+        if s.is_empty() {
+            return vec![];
+        }
+        self.iter_prefix(s).collect()
+    }
+}
+
+/* Turns the char keys of matches produced by the underlying GenericTrie into Strings. */
+fn to_string<V>((key, value): (Vec<char>, &V)) -> (String, &V) {
+    (key.into_iter().collect(), value)
+}
+
+fn to_strings<V>(matches: Vec<(Vec<char>, &V, usize)>) -> Vec<(String, &V, usize)> {
+    matches
+        .into_iter()
+        .map(|(key, value, distance)| (key.into_iter().collect(), value, distance))
+        .collect()
+}
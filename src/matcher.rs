@@ -0,0 +1,142 @@
+use crate::{
+    binary::flatten,
+    iter::{Matches, OverlappingMatches},
+    Trie,
+};
+
+/* Marks the absence of an output link. */
+pub(crate) const NO_NODE: u32 = u32::MAX;
+
+/* An Aho-Corasick automaton finding all occurrences of a Trie's strings in a text in a single pass.
+ * The nodes are packed like those of a FrozenTrie and extended by two links each: the failure link
+ * points to the node of the longest proper suffix of the node's string that is also in the Trie,
+ * the output link to the nearest node along the failure links that ends a string. */
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    child_starts: Vec<u32>,
+    labels: Vec<char>,
+    fail: Vec<u32>,
+    output: Vec<u32>,
+    terminal: Vec<bool>,
+    /* The length in bytes of the string leading up to each node. */
+    byte_lens: Vec<u32>,
+    size: usize,
+}
+
+impl Trie {
+    /* Turns the Trie into a Matcher searching texts for all of its strings at once. */
+    pub fn into_matcher(self) -> Matcher {
+        let flat_nodes = flatten(&self.map.trie.root);
+
+        let mut child_starts = Vec::with_capacity(flat_nodes.len() + 1);
+        let mut labels = Vec::with_capacity(flat_nodes.len());
+        let mut terminal = Vec::with_capacity(flat_nodes.len());
+        for node in &flat_nodes {
+            child_starts.push(node.first_child);
+            labels.push(node.label);
+            terminal.push(node.terminal);
+        }
+        child_starts.push(flat_nodes.len() as u32);
+
+        let mut matcher = Matcher {
+            child_starts,
+            labels,
+            fail: vec![0; flat_nodes.len()],
+            output: vec![NO_NODE; flat_nodes.len()],
+            terminal,
+            byte_lens: vec![0; flat_nodes.len()],
+            size: self.size(),
+        };
+        matcher.link();
+        matcher
+    }
+}
+
+impl Matcher {
+    /* Computes the failure and output links. Nodes are numbered in breadth-first order, so the links
+     * of every node (which point to shallower nodes) are known by the time its children are linked. */
+    fn link(&mut self) {
+        for parent in 0..self.labels.len() as u32 {
+            let (start, end) = self.children(parent);
+            for child in start..end {
+                let label = self.labels[child as usize];
+                self.byte_lens[child as usize] =
+                    self.byte_lens[parent as usize] + label.len_utf8() as u32;
+
+                let fail = match parent {
+                    0 => 0,
+                    _ => self.next_state(self.fail[parent as usize], label),
+                };
+                self.fail[child as usize] = fail;
+                self.output[child as usize] = self.first_output(fail);
+            }
+        }
+    }
+
+    fn children(&self, i: u32) -> (u32, u32) {
+        (
+            self.child_starts[i as usize],
+            self.child_starts[i as usize + 1],
+        )
+    }
+
+    fn child(&self, i: u32, ch: char) -> Option<u32> {
+        let (start, end) = self.children(i);
+        let labels = &self.labels[start as usize..end as usize];
+        Some(start + labels.binary_search(&ch).ok()? as u32)
+    }
+
+    /* Returns the state reached from 'state' by consuming ch, following failure links
+     * until a node with a matching child is found. */
+    pub(crate) fn next_state(&self, mut state: u32, ch: char) -> u32 {
+        loop {
+            if let Some(child) = self.child(state, ch) {
+                return child;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state as usize];
+        }
+    }
+
+    /* Returns the node of the longest string ending in 'state', or NO_NODE if there is none.
+     * Shorter ones are found by following output links from there. */
+    pub(crate) fn first_output(&self, state: u32) -> u32 {
+        if self.terminal[state as usize] {
+            state
+        } else {
+            self.output[state as usize]
+        }
+    }
+
+    pub(crate) fn next_output(&self, node: u32) -> u32 {
+        self.output[node as usize]
+    }
+
+    pub(crate) fn byte_len(&self, node: u32) -> usize {
+        self.byte_lens[node as usize] as usize
+    }
+
+    /* Returns the number of strings the Matcher searches for. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /* Returns an iterator lazily yielding the leftmost-longest matches in text as (start, end, string),
+     * where start and end are byte offsets and string is text[start..end]. Matches do not overlap:
+     * of all matches starting first the longest one is taken, and the search resumes at its end. */
+    pub fn find_iter<'t>(&self, text: &'t str) -> Matches<'_, 't> {
+        Matches::new(self, text)
+    }
+
+    /* Like find_iter(), but yields every occurrence of every string, including overlapping ones.
+     * Matches are ordered by their end, longer ones first among those ending at the same offset. */
+    pub fn find_overlapping_iter<'t>(&self, text: &'t str) -> OverlappingMatches<'_, 't> {
+        OverlappingMatches::new(self, text)
+    }
+}
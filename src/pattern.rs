@@ -0,0 +1,154 @@
+use crate::{Automaton, GenericTrie};
+
+#[derive(Debug)]
+enum Token {
+    Char(char),
+    // ?
+    Any,
+    // *
+    Star,
+    // [a-z], [abc], [!a-z] or [^a-z]
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Token {
+    /* Whether or not this token consumes ch. Star is handled separately by the matcher. */
+    fn accepts(&self, ch: char) -> bool {
+        match self {
+            Token::Char(c) => *c == ch,
+            Token::Any => true,
+            Token::Star => false,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&ch)) != *negated
+            }
+        }
+    }
+}
+
+/* A glob-like pattern supporting ? (any single char), * (any sequence of chars) and
+ * character classes like [a-z]. A backslash escapes the next char. An unclosed [ matches itself.
+ * Its states are the sorted sets of pattern positions reachable so far. */
+#[derive(Debug)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Self {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut tokens = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            let token = match chars[i] {
+                '?' => Token::Any,
+                '*' => Token::Star,
+                '\\' if i + 1 < chars.len() => {
+                    i += 1;
+                    Token::Char(chars[i])
+                }
+                '[' => match Self::parse_class(&chars[i + 1..]) {
+                    Some((token, len)) => {
+                        i += len;
+                        token
+                    }
+                    None => Token::Char('['),
+                },
+                ch => Token::Char(ch),
+            };
+            // Consecutive stars are equivalent to a single one.
+            if !(matches!(token, Token::Star) && matches!(tokens.last(), Some(Token::Star))) {
+                tokens.push(token);
+            }
+            i += 1;
+        }
+        Self { tokens }
+    }
+
+    /* Parses the body of a character class following a [.
+     * Returns the class and the number of chars it spans including the closing ], or None if it is never closed. */
+    fn parse_class(chars: &[char]) -> Option<(Token, usize)> {
+        let mut i = 0;
+        let negated = matches!(chars.first(), Some('!' | '^'));
+        if negated {
+            i += 1;
+        }
+
+        let mut ranges = vec![];
+        // A ] right after the opening [ is part of the class.
+        let mut first = true;
+        loop {
+            let lo = *chars.get(i)?;
+            if lo == ']' && !first {
+                return Some((Token::Class { negated, ranges }, i + 1));
+            }
+            first = false;
+
+            match (chars.get(i + 1), chars.get(i + 2)) {
+                (Some('-'), Some(&hi)) if hi != ']' => {
+                    ranges.push((lo, hi));
+                    i += 3;
+                }
+                _ => {
+                    ranges.push((lo, lo));
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /* Adds position to the sorted set of states, along with every position reachable from it
+     * without consuming a char (a star may match the empty sequence). */
+    fn add_state(&self, states: &mut Vec<usize>, mut position: usize) {
+        loop {
+            if let Err(i) = states.binary_search(&position) {
+                states.insert(i, position);
+            }
+            match self.tokens.get(position) {
+                Some(Token::Star) => position += 1,
+                _ => break,
+            }
+        }
+    }
+}
+
+impl Automaton for Pattern {
+    type State = Vec<usize>;
+
+    fn start(&self) -> Self::State {
+        let mut states = vec![];
+        self.add_state(&mut states, 0);
+        states
+    }
+
+    fn accept(&self, states: &Self::State, ch: char) -> Self::State {
+        let mut next_states = vec![];
+        for &position in states {
+            match self.tokens.get(position) {
+                Some(Token::Star) => self.add_state(&mut next_states, position),
+                Some(token) if token.accepts(ch) => self.add_state(&mut next_states, position + 1),
+                _ => {}
+            }
+        }
+        next_states
+    }
+
+    /* Whether or not the whole pattern has been matched in any of the states. */
+    fn is_match(&self, states: &Self::State) -> bool {
+        states.last() == Some(&self.tokens.len())
+    }
+
+    fn can_match(&self, states: &Self::State) -> bool {
+        !states.is_empty()
+    }
+}
+
+impl<V> GenericTrie<char, V> {
+    /* Returns every key matching the glob pattern, paired with its value, in lexicographic order.
+     * Only the branches of the Trie the pattern can still match are visited. */
+    pub fn matches(&self, pattern: &str) -> Vec<(Vec<char>, &V)> {
+        self.search(Pattern::new(pattern))
+    }
+}
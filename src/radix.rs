@@ -0,0 +1,280 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    mem,
+};
+
+use crate::iter::RadixKeys;
+
+#[derive(Debug)]
+pub struct RadixNode {
+    /* The part of a key leading into this node. Only the root has an empty label. */
+    pub(crate) label: String,
+    /* Children keyed by the first character of their label. */
+    pub(crate) children: BTreeMap<char, RadixNode>,
+    pub(crate) end_of_word: bool,
+}
+
+impl RadixNode {
+    fn new(label: String, end_of_word: bool) -> Self {
+        Self {
+            label,
+            children: BTreeMap::new(),
+            end_of_word,
+        }
+    }
+
+    /* Counts the strings stored in the subtree rooted at this node. */
+    fn count(&self) -> usize {
+        let mut stack = vec![self];
+        let mut count = 0;
+        while let Some(node) = stack.pop() {
+            if node.end_of_word {
+                count += 1;
+            }
+            stack.extend(node.children.values());
+        }
+        count
+    }
+}
+
+/* A compressed (radix / Patricia) Trie. Chains of nodes with a single child are collapsed
+ * into one edge, whose label holds all of their characters. */
+#[derive(Debug)]
+pub struct RadixTrie {
+    root: RadixNode,
+    size: usize,
+}
+
+impl RadixTrie {
+    pub fn new() -> Self {
+        Self {
+            root: RadixNode::new(String::new(), false),
+            size: 0,
+        }
+    }
+
+    /* Returns the number of strings in the RadixTrie. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.children.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.root.children.clear();
+        self.size = 0;
+    }
+
+    /* Ensures that s is present in the RadixTrie.
+     * Returns true only if s is not present in the RadixTrie when insert() is called. */
+    pub fn insert(&mut self, s: &str) -> bool {
+        if s.is_empty() {
+            return false;
+        }
+
+        let mut node = &mut self.root;
+        let mut rest = s;
+        while let Some(first) = rest.chars().next() {
+            let child = match node.children.entry(first) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(RadixNode::new(rest.into(), true));
+                    self.size += 1;
+                    return true;
+                }
+            };
+
+            let common = common_prefix_len(&child.label, rest);
+            if common < child.label.len() {
+                /* rest diverges from the edge (or ends) in the middle of its label, so the edge is split
+                 * into the common part and a new node carrying the remainder and all former children. */
+                let suffix = child.label.split_off(common);
+                let mut split = RadixNode::new(suffix, child.end_of_word);
+                split.children = mem::take(&mut child.children);
+                child
+                    .children
+                    .insert(split.label.chars().next().unwrap(), split);
+                child.end_of_word = false;
+            }
+
+            rest = &rest[common..];
+            node = child;
+        }
+
+        if node.end_of_word {
+            return false;
+        }
+        node.end_of_word = true;
+        self.size += 1;
+        true
+    }
+
+    /* Removes an entire string s from the RadixTrie.
+     * Returns true if and only if s was present up until removal. */
+    pub fn remove(&mut self, s: &str) -> bool {
+        if s.is_empty() {
+            return false;
+        }
+
+        let removed = Self::remove_from(&mut self.root, s);
+        if removed {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    /* Recursively descends along rest, unmarks the node it ends at and compacts
+     * the edges on the way back up. */
+    fn remove_from(node: &mut RadixNode, rest: &str) -> bool {
+        let first = rest.chars().next().unwrap();
+        let child = match node.children.get_mut(&first) {
+            Some(child) if rest.starts_with(&child.label) => child,
+            _ => return false,
+        };
+
+        let rest = &rest[child.label.len()..];
+        let removed = if rest.is_empty() {
+            mem::replace(&mut child.end_of_word, false)
+        } else {
+            Self::remove_from(child, rest)
+        };
+
+        if removed {
+            Self::compact(node, first);
+        }
+        removed
+    }
+
+    /* Removes all strings from the RadixTrie that share a common prefix s.
+     * Returns true if at least one string has been removed. */
+    pub fn remove_pref(&mut self, s: &str) -> bool {
+        if s.is_empty() {
+            return false;
+        }
+
+        match Self::remove_pref_from(&mut self.root, s) {
+            Some(count) => {
+                self.size -= count;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /* Like remove_from(), but drops the whole subtree below the edge rest ends in.
+     * Returns the number of strings removed, or None if no string starts with rest. */
+    fn remove_pref_from(node: &mut RadixNode, rest: &str) -> Option<usize> {
+        let first = rest.chars().next().unwrap();
+        let child = node.children.get_mut(&first)?;
+
+        if child.label.starts_with(rest) {
+            return node.children.remove(&first).map(|child| child.count());
+        }
+        if !rest.starts_with(&child.label) {
+            return None;
+        }
+
+        let count = Self::remove_pref_from(child, &rest[child.label.len()..])?;
+        Self::compact(node, first);
+        Some(count)
+    }
+
+    /* Restores the invariants of the child of 'node' labeled with 'first' after a removal below it:
+     * a child that no longer stores a string is dropped if it is a leaf, or merged with its only child. */
+    fn compact(node: &mut RadixNode, first: char) {
+        let child = node.children.get_mut(&first).unwrap();
+        if child.end_of_word {
+            return;
+        }
+
+        match child.children.len() {
+            0 => {
+                node.children.remove(&first);
+            }
+            1 => {
+                let (_, grandchild) = child.children.pop_first().unwrap();
+                child.label.push_str(&grandchild.label);
+                child.children = grandchild.children;
+                child.end_of_word = grandchild.end_of_word;
+            }
+            _ => {}
+        }
+    }
+
+    /* Walks along s. Returns the node s ends in (or, if s ends in the middle of an edge, the node
+     * below that edge), together with the full key leading to that node. */
+    fn find_node(&self, s: &str) -> Option<(&RadixNode, String)> {
+        let mut node = &self.root;
+        let mut key = String::new();
+        let mut rest = s;
+        while let Some(first) = rest.chars().next() {
+            let child = node.children.get(&first)?;
+            if child.label.starts_with(rest) {
+                key.push_str(&child.label);
+                return Some((child, key));
+            }
+            if !rest.starts_with(&child.label) {
+                return None;
+            }
+
+            key.push_str(&child.label);
+            rest = &rest[child.label.len()..];
+            node = child;
+        }
+        Some((node, key))
+    }
+
+    /* Whether or not s is present in the RadixTrie. */
+    pub fn contains(&self, s: &str) -> bool {
+        match self.find_node(s) {
+            Some((node, key)) => node.end_of_word && key.len() == s.len(),
+            None => false,
+        }
+    }
+
+    /* Whether or not at least one string with a prefix s is present in the RadixTrie. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.find_node(s).is_some()
+    }
+
+    /* Returns an iterator lazily yielding all strings present in the RadixTrie.
+     * The strings are yielded in lexicographic order. */
+    pub fn iter(&self) -> RadixKeys<'_> {
+        RadixKeys::new(String::new(), Some(&self.root), Some(self.size))
+    }
+
+    /* Like iter(). However, only strings that share a common prefix s are yielded. */
+    pub fn iter_prefix(&self, s: &str) -> RadixKeys<'_> {
+        match self.find_node(s) {
+            Some((node, key)) => {
+                let depth = key.len() - node.label.len();
+                RadixKeys::new(key[..depth].into(), Some(node), None)
+            }
+            None => RadixKeys::new(String::new(), None, None),
+        }
+    }
+
+    /* Builds and returns a vector holding all strings present in the RadixTrie.
+     * The strings are sorted lexicographically. */
+    pub fn as_vec(&self) -> Vec<String> {
+        self.iter().collect()
+    }
+
+    /* Like as_vec(). However, the returned vector only holds strings that share a common prefix s. */
+    pub fn as_vec_pref(&self, s: &str) -> Vec<String> {
+        if s.is_empty() {
+            return vec![];
+        }
+        self.iter_prefix(s).collect()
+    }
+}
+
+/* Returns the length in bytes of the longest common prefix of a and b. */
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i)
+}
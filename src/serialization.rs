@@ -0,0 +1,147 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{GenericTrie, RadixTrie, Trie, TrieMap};
+
+/* All tries are serialized as the list of their keys (in lexicographic order) rather than their nodes.
+ * Deserializing inserts the keys one by one, so the node structure and the subtree counts are rebuilt
+ * exactly as if the Trie had been filled by hand. */
+
+impl Serialize for Trie {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Trie {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(TrieVisitor)
+    }
+}
+
+struct TrieVisitor;
+
+impl<'de> Visitor<'de> for TrieVisitor {
+    type Value = Trie;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of strings")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut trie = Trie::new();
+        while let Some(s) = seq.next_element::<String>()? {
+            trie.insert(&s);
+        }
+        Ok(trie)
+    }
+}
+
+impl Serialize for RadixTrie {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for RadixTrie {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(RadixTrieVisitor)
+    }
+}
+
+struct RadixTrieVisitor;
+
+impl<'de> Visitor<'de> for RadixTrieVisitor {
+    type Value = RadixTrie;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of strings")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut trie = RadixTrie::new();
+        while let Some(s) = seq.next_element::<String>()? {
+            trie.insert(&s);
+        }
+        Ok(trie)
+    }
+}
+
+/* A TrieMap is serialized as a map from its keys to their values. */
+impl<V: Serialize> Serialize for TrieMap<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for TrieMap<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(TrieMapVisitor(PhantomData))
+    }
+}
+
+struct TrieMapVisitor<V>(PhantomData<V>);
+
+impl<'de, V: Deserialize<'de>> Visitor<'de> for TrieMapVisitor<V> {
+    type Value = TrieMap<V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map from strings to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut trie_map = TrieMap::new();
+        while let Some((key, value)) = map.next_entry::<String, V>()? {
+            trie_map.insert(&key, value);
+        }
+        Ok(trie_map)
+    }
+}
+
+/* A GenericTrie is serialized as a sequence of (key, value) pairs, since its keys are sequences
+ * themselves and many formats only allow strings as map keys. */
+impl<K, V> Serialize for GenericTrie<K, V>
+where
+    K: Ord + Clone + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for GenericTrie<K, V>
+where
+    K: Ord + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(GenericTrieVisitor(PhantomData))
+    }
+}
+
+struct GenericTrieVisitor<K, V>(PhantomData<(K, V)>);
+
+impl<'de, K, V> Visitor<'de> for GenericTrieVisitor<K, V>
+where
+    K: Ord + Clone + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    type Value = GenericTrie<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of (key, value) pairs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut trie = GenericTrie::new();
+        while let Some((key, value)) = seq.next_element::<(Vec<K>, V)>()? {
+            trie.insert(key, value);
+        }
+        Ok(trie)
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::btree_map::Entry;
+
+use crate::{GenericTrie, Trie, TrieMap, TrieNode};
+
+type Node = TrieNode<char, ()>;
+
+/* Set operations between two Tries. Both Tries are walked together node by node, so shared prefixes
+ * are compared only once and subtrees present on only one side are copied or dropped as a whole. */
+impl Trie {
+    fn from_root(root: Node) -> Self {
+        Self {
+            map: TrieMap {
+                trie: GenericTrie { root },
+            },
+        }
+    }
+
+    fn root(&self) -> &Node {
+        &self.map.trie.root
+    }
+
+    fn root_mut(&mut self) -> &mut Node {
+        &mut self.map.trie.root
+    }
+
+    /* Returns a Trie holding every string present in self or other (or both). */
+    pub fn union(&self, other: &Trie) -> Trie {
+        let mut root = self.root().clone();
+        union_with(&mut root, other.root());
+        Self::from_root(root)
+    }
+
+    /* Returns a Trie holding every string present in both self and other. */
+    pub fn intersection(&self, other: &Trie) -> Trie {
+        Self::from_root(intersection(self.root(), other.root()))
+    }
+
+    /* Returns a Trie holding every string present in self, but not in other. */
+    pub fn difference(&self, other: &Trie) -> Trie {
+        let mut root = self.root().clone();
+        difference_with(&mut root, other.root());
+        Self::from_root(root)
+    }
+
+    /* Returns a Trie holding every string present in exactly one of self and other. */
+    pub fn symmetric_difference(&self, other: &Trie) -> Trie {
+        let mut root = self.root().clone();
+        symmetric_difference_with(&mut root, other.root());
+        Self::from_root(root)
+    }
+
+    /* Whether or not every string in self is also present in other. */
+    pub fn is_subset(&self, other: &Trie) -> bool {
+        is_subset(self.root(), other.root())
+    }
+
+    /* Whether or not every string in other is also present in self. */
+    pub fn is_superset(&self, other: &Trie) -> bool {
+        is_subset(other.root(), self.root())
+    }
+
+    /* Whether or not self and other have no string in common. */
+    pub fn is_disjoint(&self, other: &Trie) -> bool {
+        is_disjoint(self.root(), other.root())
+    }
+
+    /* Like union(), but adds the strings of other to self in place. */
+    pub fn union_with(&mut self, other: &Trie) {
+        union_with(self.root_mut(), other.root());
+    }
+
+    /* Like intersection(), but removes the strings missing from other from self in place. */
+    pub fn intersection_with(&mut self, other: &Trie) {
+        intersection_with(self.root_mut(), other.root());
+    }
+
+    /* Like difference(), but removes the strings of other from self in place. */
+    pub fn difference_with(&mut self, other: &Trie) {
+        difference_with(self.root_mut(), other.root());
+    }
+
+    /* Like symmetric_difference(), but updates self in place. */
+    pub fn symmetric_difference_with(&mut self, other: &Trie) {
+        symmetric_difference_with(self.root_mut(), other.root());
+    }
+}
+
+/* Recomputes the subtree count of node from its own value and the counts of its children. */
+fn recount(node: &mut Node) {
+    node.count = usize::from(node.value.is_some())
+        + node.map.values().map(|child| child.count).sum::<usize>();
+}
+
+fn union_with(node: &mut Node, other: &Node) {
+    if other.value.is_some() {
+        node.value = Some(());
+    }
+    for (ch, other_child) in &other.map {
+        match node.map.entry(*ch) {
+            Entry::Occupied(entry) => union_with(entry.into_mut(), other_child),
+            Entry::Vacant(entry) => {
+                entry.insert(other_child.clone());
+            }
+        }
+    }
+    recount(node);
+}
+
+/* Only the children present on both sides are visited; the result is built from scratch,
+ * so nothing outside of the intersection is ever copied. */
+fn intersection(node: &Node, other: &Node) -> Node {
+    let mut result = Node::new();
+    if node.value.is_some() && other.value.is_some() {
+        result.value = Some(());
+    }
+    for (ch, child) in &node.map {
+        if let Some(other_child) = other.map.get(ch) {
+            let result_child = intersection(child, other_child);
+            if result_child.count > 0 {
+                result.map.insert(*ch, result_child);
+            }
+        }
+    }
+    recount(&mut result);
+    result
+}
+
+fn intersection_with(node: &mut Node, other: &Node) {
+    if other.value.is_none() {
+        node.value = None;
+    }
+    node.map.retain(|ch, child| match other.map.get(ch) {
+        Some(other_child) => {
+            intersection_with(child, other_child);
+            child.count > 0
+        }
+        None => false,
+    });
+    recount(node);
+}
+
+fn difference_with(node: &mut Node, other: &Node) {
+    if other.value.is_some() {
+        node.value = None;
+    }
+    node.map.retain(|ch, child| match other.map.get(ch) {
+        Some(other_child) => {
+            difference_with(child, other_child);
+            child.count > 0
+        }
+        None => true,
+    });
+    recount(node);
+}
+
+fn symmetric_difference_with(node: &mut Node, other: &Node) {
+    if other.value.is_some() {
+        node.value = match node.value {
+            Some(()) => None,
+            None => Some(()),
+        };
+    }
+    for (ch, other_child) in &other.map {
+        match node.map.entry(*ch) {
+            Entry::Occupied(mut entry) => {
+                symmetric_difference_with(entry.get_mut(), other_child);
+                if entry.get().count == 0 {
+                    entry.remove();
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(other_child.clone());
+            }
+        }
+    }
+    recount(node);
+}
+
+/* Stops at the first node holding more strings than its counterpart, or lacking one. */
+fn is_subset(node: &Node, other: &Node) -> bool {
+    if node.count > other.count || (node.value.is_some() && other.value.is_none()) {
+        return false;
+    }
+    node.map.iter().all(|(ch, child)| {
+        other
+            .map
+            .get(ch)
+            .is_some_and(|other_child| is_subset(child, other_child))
+    })
+}
+
+/* Stops at the first string found on both sides. Only children present on both sides are visited,
+ * looked up from the side with fewer of them. */
+fn is_disjoint(node: &Node, other: &Node) -> bool {
+    if node.value.is_some() && other.value.is_some() {
+        return false;
+    }
+    let (fewer, more) = if node.map.len() <= other.map.len() {
+        (node, other)
+    } else {
+        (other, node)
+    };
+    fewer.map.iter().all(|(ch, child)| {
+        more.map
+            .get(ch)
+            .is_none_or(|other_child| is_disjoint(child, other_child))
+    })
+}
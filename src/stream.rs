@@ -0,0 +1,205 @@
+use std::{io::Read, mem, str};
+
+use crate::{iter::ReadMatches, matcher::NO_NODE, Matcher};
+
+/* Searches a text arriving in chunks for the strings of a Matcher, e.g. a file or a socket.
+ * The automaton state is carried across chunk boundaries, so matches spanning several chunks are
+ * found as well, and all offsets are absolute byte offsets into the whole text. Only the tail of the
+ * text that a match could still start in is kept, which is never longer than the longest string. */
+#[derive(Debug, Clone)]
+pub struct StreamMatcher<'m> {
+    matcher: &'m Matcher,
+    overlapping: bool,
+    /* The text from 'offset' on that has not been consumed yet or may still be part of a match. */
+    buffer: String,
+    offset: usize,
+    /* The index into buffer of the next char to consume. */
+    pos: usize,
+    state: u32,
+    /* Like in Matches, the leftmost-longest match found so far, in absolute offsets. */
+    candidate: Option<(usize, usize)>,
+    /* The bytes of a char cut off at the end of the last byte chunk. */
+    partial: Vec<u8>,
+}
+
+impl Matcher {
+    /* Returns a StreamMatcher reporting the leftmost-longest matches, like find_iter(). */
+    pub fn stream(&self) -> StreamMatcher<'_> {
+        StreamMatcher::new(self, false)
+    }
+
+    /* Returns a StreamMatcher reporting all matches, like find_overlapping_iter(). */
+    pub fn overlapping_stream(&self) -> StreamMatcher<'_> {
+        StreamMatcher::new(self, true)
+    }
+
+    /* Returns an iterator lazily reading 'reader' in chunks and yielding the leftmost-longest matches
+     * in its data as (start, end, string). An error while reading is yielded once and ends the iteration. */
+    pub fn find_read<R: Read>(&self, reader: R) -> ReadMatches<'_, R> {
+        ReadMatches::new(self.stream(), reader)
+    }
+
+    /* Like find_read(), but yields all matches, like find_overlapping_iter(). */
+    pub fn find_overlapping_read<R: Read>(&self, reader: R) -> ReadMatches<'_, R> {
+        ReadMatches::new(self.overlapping_stream(), reader)
+    }
+}
+
+impl<'m> StreamMatcher<'m> {
+    fn new(matcher: &'m Matcher, overlapping: bool) -> Self {
+        Self {
+            matcher,
+            overlapping,
+            buffer: String::new(),
+            offset: 0,
+            pos: 0,
+            state: 0,
+            candidate: None,
+            partial: vec![],
+        }
+    }
+
+    /* Consumes the next chunk of text. Returns the matches that are known to be complete,
+     * as (start, end, string) with start and end being byte offsets into the whole text. */
+    pub fn feed_str(&mut self, chunk: &str) -> Vec<(usize, usize, String)> {
+        let mut matches = vec![];
+        // A str chunk starts with a whole char, so a cut-off char before it can never be completed.
+        self.skip_partial(&mut matches);
+        self.buffer.push_str(chunk);
+        self.scan(&mut matches);
+        matches
+    }
+
+    /* Like feed_str(), but for chunks of UTF-8 encoded bytes. A char may be split between two chunks.
+     * Invalid bytes are skipped, but counted in the offsets; no match is found across them. */
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<(usize, usize, String)> {
+        let mut matches = vec![];
+
+        let joined;
+        let mut rest = if self.partial.is_empty() {
+            chunk
+        } else {
+            joined = [mem::take(&mut self.partial).as_slice(), chunk].concat();
+            joined.as_slice()
+        };
+
+        loop {
+            match str::from_utf8(rest) {
+                Ok(s) => {
+                    self.buffer.push_str(s);
+                    self.scan(&mut matches);
+                    break;
+                }
+                Err(error) => {
+                    let (valid, invalid) = rest.split_at(error.valid_up_to());
+                    self.buffer.push_str(str::from_utf8(valid).unwrap());
+                    self.scan(&mut matches);
+
+                    match error.error_len() {
+                        // The chunk ends in the middle of a char, which the next chunk may complete.
+                        None => {
+                            self.partial = invalid.to_vec();
+                            break;
+                        }
+                        Some(len) => {
+                            self.end_text(&mut matches);
+                            self.offset += len;
+                            rest = &invalid[len..];
+                        }
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+
+    /* Ends the text. Returns the remaining matches, which could not be reported before because
+     * a longer one might have followed. Feeding more chunks afterwards starts a new text whose
+     * offsets continue where this one ended. */
+    pub fn finish(&mut self) -> Vec<(usize, usize, String)> {
+        let mut matches = vec![];
+        self.skip_partial(&mut matches);
+        self.end_text(&mut matches);
+        matches
+    }
+
+    fn report(&self, start: usize, end: usize, matches: &mut Vec<(usize, usize, String)>) {
+        let s = &self.buffer[start - self.offset..end - self.offset];
+        matches.push((start, end, s.into()));
+    }
+
+    /* Consumes the buffered text, working like Matches or OverlappingMatches. Afterwards, the buffer
+     * is cut down to the text any match still to be reported may start in. */
+    fn scan(&mut self, matches: &mut Vec<(usize, usize, String)>) {
+        while let Some(ch) = self.buffer[self.pos..].chars().next() {
+            self.pos += ch.len_utf8();
+            self.state = self.matcher.next_state(self.state, ch);
+            let end = self.offset + self.pos;
+
+            let mut node = self.matcher.first_output(self.state);
+            if self.overlapping {
+                while node != NO_NODE {
+                    self.report(end - self.matcher.byte_len(node), end, matches);
+                    node = self.matcher.next_output(node);
+                }
+                continue;
+            }
+
+            if node != NO_NODE {
+                let start = end - self.matcher.byte_len(node);
+                if self
+                    .candidate
+                    .is_none_or(|(first_start, _)| start <= first_start)
+                {
+                    self.candidate = Some((start, end));
+                }
+            }
+            if let Some((start, _)) = self.candidate {
+                if end - self.matcher.byte_len(self.state) > start {
+                    self.take_candidate(matches);
+                }
+            }
+        }
+
+        /* Matches still to come start within the string of the current state. So does the candidate,
+         * since it would have been reported otherwise. */
+        let keep = self.offset + self.pos - self.matcher.byte_len(self.state);
+        self.buffer.drain(..keep - self.offset);
+        self.pos -= keep - self.offset;
+        self.offset = keep;
+    }
+
+    /* Reports the candidate and restarts the search at its end. */
+    fn take_candidate(&mut self, matches: &mut Vec<(usize, usize, String)>) -> bool {
+        match self.candidate.take() {
+            Some((start, end)) => {
+                self.report(start, end, matches);
+                self.pos = end - self.offset;
+                self.state = 0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /* Reports the remaining matches as if the text ended here, and empties the buffer. */
+    fn end_text(&mut self, matches: &mut Vec<(usize, usize, String)>) {
+        while self.take_candidate(matches) {
+            self.scan(matches);
+        }
+        self.offset += self.buffer.len();
+        self.buffer.clear();
+        self.pos = 0;
+        self.state = 0;
+    }
+
+    /* Skips the bytes of a cut-off char, which cannot be part of any match. */
+    fn skip_partial(&mut self, matches: &mut Vec<(usize, usize, String)>) {
+        if !self.partial.is_empty() {
+            self.end_text(matches);
+            self.offset += self.partial.len();
+            self.partial.clear();
+        }
+    }
+}
@@ -1,30 +1,330 @@
-use crate::Trie;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+use crate::{
+    iter::{
+        DawgKeys, FrozenKeys, FstStream, IntoIter, IntoKeys, Iter, Keys, MapIntoIter, MapIter,
+        RadixKeys,
+    },
+    Dawg, DawgBuilder, FrozenTrie, Fst, FstBuilder, GenericTrie, RadixTrie, Trie, TrieMap,
+    WeightedTrie,
+};
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Default for DawgBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Default for FstBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Default for RadixTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Default for WeightedTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<K: Ord + Clone, V> Default for GenericTrie<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<V> Default for TrieMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl From<&Vec<String>> for Trie {
     fn from(sequence: &Vec<String>) -> Self {
-        let mut trie = Self::new();
-        for s in sequence {
-            trie.insert(&s);
-        }
-        trie
+        sequence.iter().collect()
     }
 }
 impl From<Vec<String>> for Trie {
     fn from(sequence: Vec<String>) -> Self {
-        return Self::from(&sequence);
+        Self::from(&sequence)
     }
 }
 impl From<&Vec<&str>> for Trie {
     fn from(sequence: &Vec<&str>) -> Self {
+        sequence.iter().collect()
+    }
+}
+impl From<Vec<&str>> for Trie {
+    fn from(sequence: Vec<&str>) -> Self {
+        Self::from(&sequence)
+    }
+}
+impl<T: AsRef<str>> From<&[T]> for Trie {
+    fn from(sequence: &[T]) -> Self {
+        sequence.iter().collect()
+    }
+}
+impl<T: AsRef<str>, const N: usize> From<[T; N]> for Trie {
+    fn from(sequence: [T; N]) -> Self {
+        sequence.into_iter().collect()
+    }
+}
+
+/* Empty strings are skipped, just like when inserting them one by one. */
+impl<T: AsRef<str>> FromIterator<T> for Trie {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut trie = Self::new();
-        for s in sequence {
-            trie.insert(s);
+        trie.extend(iter);
+        trie
+    }
+}
+impl<T: AsRef<str>> Extend<T> for Trie {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for s in iter {
+            self.insert(s.as_ref());
         }
+    }
+}
+
+/* Later values replace earlier ones for the same key, just like insert(). */
+impl<S: AsRef<str>, V> FromIterator<(S, V)> for TrieMap<V> {
+    fn from_iter<I: IntoIterator<Item = (S, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+impl<S: AsRef<str>, V> Extend<(S, V)> for TrieMap<V> {
+    fn extend<I: IntoIterator<Item = (S, V)>>(&mut self, iter: I) {
+        for (s, value) in iter {
+            self.insert(s.as_ref(), value);
+        }
+    }
+}
+impl<K: Ord + Clone, V, J: IntoIterator<Item = K>> FromIterator<(J, V)> for GenericTrie<K, V> {
+    fn from_iter<I: IntoIterator<Item = (J, V)>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        trie.extend(iter);
         trie
     }
 }
-impl From<Vec<&str>> for Trie {
+impl<K: Ord + Clone, V, J: IntoIterator<Item = K>> Extend<(J, V)> for GenericTrie<K, V> {
+    fn extend<I: IntoIterator<Item = (J, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/* Sorts and deduplicates the strings, as required by the DawgBuilder. */
+fn build_dawg<'a>(strings: impl Iterator<Item = &'a str>) -> Dawg {
+    let mut strings: Vec<&str> = strings.collect();
+    strings.sort_unstable();
+    strings.dedup();
+
+    let mut builder = DawgBuilder::new();
+    for s in strings {
+        builder.insert(s);
+    }
+    builder.finish()
+}
+impl From<&Vec<String>> for Dawg {
+    fn from(sequence: &Vec<String>) -> Self {
+        build_dawg(sequence.iter().map(String::as_str))
+    }
+}
+impl From<Vec<String>> for Dawg {
+    fn from(sequence: Vec<String>) -> Self {
+        Self::from(&sequence)
+    }
+}
+impl From<&Vec<&str>> for Dawg {
+    fn from(sequence: &Vec<&str>) -> Self {
+        build_dawg(sequence.iter().copied())
+    }
+}
+impl From<Vec<&str>> for Dawg {
     fn from(sequence: Vec<&str>) -> Self {
-        return Self::from(&sequence);
+        Self::from(&sequence)
+    }
+}
+/* A Trie already yields its strings in order, so no sorting is needed. */
+impl From<&Trie> for Dawg {
+    fn from(trie: &Trie) -> Self {
+        let mut builder = DawgBuilder::new();
+        for s in trie {
+            builder.insert(&s);
+        }
+        builder.finish()
+    }
+}
+
+/* Sorts the pairs by key, as required by the FstBuilder. Of pairs with equal keys, the first one is kept. */
+impl From<Vec<(&str, u64)>> for Fst {
+    fn from(mut pairs: Vec<(&str, u64)>) -> Self {
+        pairs.sort_by_key(|&(key, _)| key);
+
+        let mut builder = FstBuilder::new();
+        for (key, value) in pairs {
+            builder.insert(key, value);
+        }
+        builder.finish()
+    }
+}
+/* A TrieMap already yields its keys in order, so no sorting is needed. */
+impl From<&TrieMap<u64>> for Fst {
+    fn from(trie_map: &TrieMap<u64>) -> Self {
+        let mut builder = FstBuilder::new();
+        for (key, value) in trie_map {
+            builder.insert(&key, *value);
+        }
+        builder.finish()
+    }
+}
+
+impl<'a, K: Ord + Clone, V> IntoIterator for &'a GenericTrie<K, V> {
+    type Item = (Vec<K>, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<K: Ord + Clone, V> IntoIterator for GenericTrie<K, V> {
+    type Item = (Vec<K>, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.root)
+    }
+}
+impl<'a, V> IntoIterator for &'a TrieMap<V> {
+    type Item = (String, &'a V);
+    type IntoIter = MapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<V> IntoIterator for TrieMap<V> {
+    type Item = (String, V);
+    type IntoIter = MapIntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MapIntoIter {
+            iter: self.trie.into_iter(),
+        }
+    }
+}
+impl<'a> IntoIterator for &'a Trie {
+    type Item = String;
+    type IntoIter = Keys<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a> IntoIterator for &'a RadixTrie {
+    type Item = String;
+    type IntoIter = RadixKeys<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a> IntoIterator for &'a FrozenTrie {
+    type Item = String;
+    type IntoIter = FrozenKeys<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a> IntoIterator for &'a Dawg {
+    type Item = String;
+    type IntoIter = DawgKeys<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a> IntoIterator for &'a Fst {
+    type Item = (String, u64);
+    type IntoIter = FstStream<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl IntoIterator for Trie {
+    type Item = String;
+    type IntoIter = IntoKeys;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoKeys {
+            iter: self.map.into_iter(),
+        }
+    }
+}
+
+impl BitOr<&Trie> for &Trie {
+    type Output = Trie;
+
+    fn bitor(self, other: &Trie) -> Trie {
+        self.union(other)
+    }
+}
+
+impl BitAnd<&Trie> for &Trie {
+    type Output = Trie;
+
+    fn bitand(self, other: &Trie) -> Trie {
+        self.intersection(other)
+    }
+}
+
+impl Sub<&Trie> for &Trie {
+    type Output = Trie;
+
+    fn sub(self, other: &Trie) -> Trie {
+        self.difference(other)
+    }
+}
+
+impl BitXor<&Trie> for &Trie {
+    type Output = Trie;
+
+    fn bitxor(self, other: &Trie) -> Trie {
+        self.symmetric_difference(other)
+    }
+}
+
+impl BitOrAssign<&Trie> for Trie {
+    fn bitor_assign(&mut self, other: &Trie) {
+        self.union_with(other);
+    }
+}
+
+impl BitAndAssign<&Trie> for Trie {
+    fn bitand_assign(&mut self, other: &Trie) {
+        self.intersection_with(other);
+    }
+}
+
+impl SubAssign<&Trie> for Trie {
+    fn sub_assign(&mut self, other: &Trie) {
+        self.difference_with(other);
+    }
+}
+
+impl BitXorAssign<&Trie> for Trie {
+    fn bitxor_assign(&mut self, other: &Trie) {
+        self.symmetric_difference_with(other);
     }
 }
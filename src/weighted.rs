@@ -0,0 +1,273 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BinaryHeap},
+};
+
+#[derive(Debug)]
+struct WeightedNode {
+    map: BTreeMap<char, WeightedNode>,
+    weight: Option<u64>,
+    /* The greatest weight of all strings in the subtree rooted at this node. */
+    max_weight: u64,
+}
+
+impl WeightedNode {
+    fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+            weight: None,
+            max_weight: 0,
+        }
+    }
+
+    /* Recomputes max_weight after the node's weight or children have changed. */
+    fn update_max_weight(&mut self) {
+        self.max_weight = self
+            .map
+            .values()
+            .map(|node| node.max_weight)
+            .chain(self.weight)
+            .max()
+            .unwrap_or(0);
+    }
+
+    fn count(&self) -> usize {
+        usize::from(self.weight.is_some())
+            + self.map.values().map(WeightedNode::count).sum::<usize>()
+    }
+}
+
+/* A Trie whose strings carry a weight, e.g. their popularity. Every node knows the greatest weight
+ * below it, so the heaviest completions of a prefix can be found without visiting the whole subtree. */
+#[derive(Debug)]
+pub struct WeightedTrie {
+    root: WeightedNode,
+    size: usize,
+}
+
+impl WeightedTrie {
+    pub fn new() -> Self {
+        Self {
+            root: WeightedNode::new(),
+            size: 0,
+        }
+    }
+
+    /* Returns the number of strings in the WeightedTrie. */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.root = WeightedNode::new();
+        self.size = 0;
+    }
+
+    /* Stores s with the given weight. Empty strings are not stored.
+     * Returns the weight s had before, if it was present. */
+    pub fn insert(&mut self, s: &str, weight: u64) -> Option<u64> {
+        if s.is_empty() {
+            return None;
+        }
+
+        let chars: Vec<char> = s.chars().collect();
+        let old_weight = Self::insert_into(&mut self.root, &chars, weight);
+        if old_weight.is_none() {
+            self.size += 1;
+        }
+        old_weight
+    }
+
+    /* Recursively descends along rest, fixing up the maximum weights on the way back up. */
+    fn insert_into(node: &mut WeightedNode, rest: &[char], weight: u64) -> Option<u64> {
+        let old_weight = match rest.split_first() {
+            Some((ch, rest)) => {
+                let next_node = node.map.entry(*ch).or_insert_with(WeightedNode::new);
+                Self::insert_into(next_node, rest, weight)
+            }
+            None => node.weight.replace(weight),
+        };
+        node.update_max_weight();
+        old_weight
+    }
+
+    /* Returns the weight of s. */
+    pub fn get(&self, s: &str) -> Option<u64> {
+        self.find_node(s)?.weight
+    }
+
+    fn find_node(&self, s: &str) -> Option<&WeightedNode> {
+        let mut node = &self.root;
+        for ch in s.chars() {
+            node = node.map.get(&ch)?;
+        }
+        Some(node)
+    }
+
+    /* Removes s from the WeightedTrie.
+     * Returns the weight of s up until removal. */
+    pub fn remove(&mut self, s: &str) -> Option<u64> {
+        if s.is_empty() {
+            return None;
+        }
+
+        let chars: Vec<char> = s.chars().collect();
+        let weight = Self::remove_from(&mut self.root, &chars)?;
+        self.size -= 1;
+        Some(weight)
+    }
+
+    /* Recursively descends along rest and removes the string ending there. On the way back up,
+     * nodes left without strings below them are dropped and the maximum weights are fixed up. */
+    fn remove_from(node: &mut WeightedNode, rest: &[char]) -> Option<u64> {
+        let weight = match rest.split_first() {
+            Some((ch, rest)) => {
+                let next_node = node.map.get_mut(ch)?;
+                let weight = Self::remove_from(next_node, rest)?;
+                if next_node.weight.is_none() && next_node.map.is_empty() {
+                    node.map.remove(ch);
+                }
+                weight
+            }
+            None => node.weight.take()?,
+        };
+        node.update_max_weight();
+        Some(weight)
+    }
+
+    /* Removes all strings from the WeightedTrie that share a common prefix s.
+     * Returns true if at least one string has been removed. */
+    pub fn remove_pref(&mut self, s: &str) -> bool {
+        if s.is_empty() {
+            return false;
+        }
+
+        let chars: Vec<char> = s.chars().collect();
+        match Self::remove_pref_from(&mut self.root, &chars) {
+            Some(count) => {
+                self.size -= count;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /* Like remove_from(), but drops the whole subtree at the end of the prefix.
+     * Returns the number of strings removed. */
+    fn remove_pref_from(node: &mut WeightedNode, prefix: &[char]) -> Option<usize> {
+        let (ch, rest) = prefix.split_first().unwrap();
+        let count = if rest.is_empty() {
+            node.map.remove(ch)?.count()
+        } else {
+            let next_node = node.map.get_mut(ch)?;
+            let count = Self::remove_pref_from(next_node, rest)?;
+            if next_node.weight.is_none() && next_node.map.is_empty() {
+                node.map.remove(ch);
+            }
+            count
+        };
+        node.update_max_weight();
+        Some(count)
+    }
+
+    /* Whether or not s is present in the WeightedTrie. */
+    pub fn contains(&self, s: &str) -> bool {
+        self.get(s).is_some()
+    }
+
+    /* Whether or not at least one string with a prefix s is present in the WeightedTrie. */
+    pub fn contains_pref(&self, s: &str) -> bool {
+        self.find_node(s).is_some()
+    }
+
+    /* Returns the k heaviest strings sharing a common prefix s, paired with their weights.
+     * They are ordered by descending weight, ties broken lexicographically.
+     * Subtrees are explored best first by their maximum weight, so only the branches
+     * that can still contribute to the result are visited. */
+    pub fn top_k(&self, s: &str, k: usize) -> Vec<(String, u64)> {
+        let mut results = vec![];
+        let node = match self.find_node(s) {
+            Some(node) if k > 0 && (node.weight.is_some() || !node.map.is_empty()) => node,
+            _ => return results,
+        };
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Candidate {
+            weight: node.max_weight,
+            key: s.into(),
+            node: Some(node),
+        });
+
+        while let Some(candidate) = heap.pop() {
+            let node = match candidate.node {
+                Some(node) => node,
+                None => {
+                    results.push((candidate.key, candidate.weight));
+                    if results.len() == k {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            if let Some(weight) = node.weight {
+                heap.push(Candidate {
+                    weight,
+                    key: candidate.key.clone(),
+                    node: None,
+                });
+            }
+            for (ch, next_node) in node.map.iter() {
+                let mut key = candidate.key.clone();
+                key.push(*ch);
+                heap.push(Candidate {
+                    weight: next_node.max_weight,
+                    key,
+                    node: Some(next_node),
+                });
+            }
+        }
+
+        results
+    }
+}
+
+/* An entry of the top_k() search: either a complete string (node is None) or a subtree whose
+ * strings weigh at most 'weight'. The heaviest entry is popped first, ties going to the smaller key. */
+struct Candidate<'a> {
+    weight: u64,
+    key: String,
+    node: Option<&'a WeightedNode>,
+}
+
+impl Candidate<'_> {
+    /* All strings in a subtree are greater than or equal to its key, so no string can be
+     * popped before a lighter or smaller one still hidden in a subtree. */
+    fn priority(&self) -> (u64, Reverse<&String>) {
+        (self.weight, Reverse(&self.key))
+    }
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority() == other.priority()
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority().cmp(&other.priority())
+    }
+}